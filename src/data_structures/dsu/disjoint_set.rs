@@ -0,0 +1,186 @@
+// Based on "Efficiency of a Good But Not Linear Set Union Algorithm" by Robert E. Tarjan.
+//
+// Every set is a tree whose root represents it. Union links the root of the smaller tree to the
+// root of the larger one, and find links every vertex on its path directly to the root, which
+// makes both O(α(n)) amortized.
+
+/// Partition of the elements `0..n` into disjoint sets.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl DisjointSet {
+    /// Create `n` sets, each with one of the elements `0..n`.
+    pub fn new(n: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Return the number of elements.
+    pub fn size(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Return the number of sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Return the element representing the set of `x`.
+    ///
+    /// ##Panics
+    /// If `x` isn't an element.
+    pub fn find(&mut self, x: usize) -> usize {
+        assert!(x < self.parents.len(),
+                "Called find with x = {} >= size = {}",
+                x,
+                self.parents.len());
+
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = x;
+        while current != root {
+            let parent = self.parents[current];
+            self.parents[current] = root;
+            current = parent;
+        }
+
+        root
+    }
+
+    /// Merge the sets of `x` and `y`. Return `false` if they're already the same set.
+    ///
+    /// ##Panics
+    /// If `x` or `y` isn't an element.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.find(x), self.find(y));
+
+        if x == y {
+            return false;
+        }
+
+        let (small, large) = if self.sizes[x] < self.sizes[y] {
+            (x, y)
+        } else {
+            (y, x)
+        };
+
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.sets -= 1;
+
+        true
+    }
+
+    /// Return `true` if `x` and `y` are in the same set.
+    ///
+    /// ##Panics
+    /// If `x` or `y` isn't an element.
+    pub fn same_set(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Return the number of elements in the set of `x`.
+    ///
+    /// ##Panics
+    /// If `x` isn't an element.
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+
+        self.sizes[root]
+    }
+
+    /// Return the elements of every set in increasing order, with the sets ordered by their
+    /// smallest elements.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut indices = vec![None; self.parents.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.sets);
+
+        for x in 0..self.parents.len() {
+            let root = self.find(x);
+
+            let index = *indices[root].get_or_insert(sets.len());
+            if index == sets.len() {
+                sets.push(Vec::with_capacity(self.sizes[root]));
+            }
+
+            sets[index].push(x);
+        }
+
+        sets
+    }
+}
+
+#[test]
+fn test_union_find() {
+    let mut sets = DisjointSet::new(6);
+
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+
+    assert_eq!(sets.find(0), sets.find(3));
+    assert!(sets.find(0) != sets.find(4));
+    assert_eq!(3, sets.set_count());
+    assert_eq!(6, sets.size());
+}
+
+#[test]
+fn test_sets() {
+    let mut sets = DisjointSet::new(7);
+
+    sets.union(5, 1);
+    sets.union(3, 6);
+    sets.union(6, 1);
+
+    assert!(sets.same_set(3, 5));
+    assert!(!sets.same_set(0, 5));
+    assert_eq!(4, sets.set_size(6));
+    assert_eq!(1, sets.set_size(4));
+    assert_eq!(vec![vec![0], vec![1, 3, 5, 6], vec![2], vec![4]], sets.sets());
+}
+
+#[test]
+fn test_union_by_size() {
+    let mut sets = DisjointSet::new(5);
+
+    sets.union(0, 1);
+    sets.union(0, 2);
+
+    // The larger set keeps its representative.
+    let root = sets.find(0);
+    sets.union(3, 0);
+    assert_eq!(root, sets.find(3));
+
+    sets.union(4, 3);
+    assert_eq!(root, sets.find(4));
+}
+
+#[test]
+fn test_path_compression() {
+    let mut sets = DisjointSet::new(4);
+
+    // Link roots by hand into the path 0 -> 1 -> 2 -> 3.
+    sets.parents = vec![1, 2, 3, 3];
+
+    assert_eq!(3, sets.find(0));
+    assert_eq!(vec![3, 3, 3, 3], sets.parents);
+}
+
+#[test]
+#[should_panic]
+fn test_find_missing() {
+    let mut sets = DisjointSet::new(2);
+
+    sets.find(2);
+}
@@ -0,0 +1,5 @@
+pub use self::disjoint_set::DisjointSet;
+pub use self::rollback::RollbackDisjointSet;
+
+mod disjoint_set;
+mod rollback;
@@ -0,0 +1,264 @@
+// Disjoint set whose unions can be undone in the reverse order, like in offline dynamic
+// connectivity, where edges live during intervals of queries and are added and removed along a
+// divide and conquer over these intervals.
+//
+// Path compression would change parents that later unions don't record, so there's none.
+// Union by size alone keeps trees O(log n) high, which makes find O(log n) and undo O(1).
+
+/// Partition of the elements `0..n` into disjoint sets, with unions which can be undone.
+#[derive(Clone, Debug)]
+pub struct RollbackDisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+    history: Vec<Option<usize>>,
+}
+
+impl RollbackDisjointSet {
+    /// Create `n` sets, each with one of the elements `0..n`.
+    pub fn new(n: usize) -> RollbackDisjointSet {
+        RollbackDisjointSet {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            sets: n,
+            history: Vec::new(),
+        }
+    }
+
+    /// Return the number of elements.
+    pub fn size(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Return the number of sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Return the element representing the set of `x`.
+    ///
+    /// ##Panics
+    /// If `x` isn't an element.
+    pub fn find(&self, x: usize) -> usize {
+        assert!(x < self.parents.len(),
+                "Called find with x = {} >= size = {}",
+                x,
+                self.parents.len());
+
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        root
+    }
+
+    /// Merge the sets of `x` and `y`. Return `false` if they're already the same set.
+    ///
+    /// Either way, the call can be undone by `undo`.
+    ///
+    /// ##Panics
+    /// If `x` or `y` isn't an element.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.find(x), self.find(y));
+
+        if x == y {
+            self.history.push(None);
+            return false;
+        }
+
+        let (small, large) = if self.sizes[x] < self.sizes[y] {
+            (x, y)
+        } else {
+            (y, x)
+        };
+
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.sets -= 1;
+        self.history.push(Some(small));
+
+        true
+    }
+
+    /// Undo the last `union` which wasn't undone yet. Return `false` if there's none.
+    pub fn undo(&mut self) -> bool {
+        let small = match self.history.pop() {
+            None => return false,
+            Some(None) => return true,
+            Some(Some(small)) => small,
+        };
+
+        let large = self.parents[small];
+
+        self.parents[small] = small;
+        self.sizes[large] -= self.sizes[small];
+        self.sets += 1;
+
+        true
+    }
+
+    /// Return the number of unions which weren't undone, to roll back to later.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undo unions until only `snapshot` of them are left.
+    ///
+    /// ##Panics
+    /// If fewer than `snapshot` unions are left.
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len(),
+                "Called rollback with snapshot = {} > unions = {}",
+                snapshot,
+                self.history.len());
+
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+
+    /// Return `true` if `x` and `y` are in the same set.
+    ///
+    /// ##Panics
+    /// If `x` or `y` isn't an element.
+    pub fn same_set(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Return the number of elements in the set of `x`.
+    ///
+    /// ##Panics
+    /// If `x` isn't an element.
+    pub fn set_size(&self, x: usize) -> usize {
+        self.sizes[self.find(x)]
+    }
+
+    /// Return the elements of every set in increasing order, with the sets ordered by their
+    /// smallest elements.
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut indices = vec![None; self.parents.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.sets);
+
+        for x in 0..self.parents.len() {
+            let root = self.find(x);
+
+            let index = *indices[root].get_or_insert(sets.len());
+            if index == sets.len() {
+                sets.push(Vec::with_capacity(self.sizes[root]));
+            }
+
+            sets[index].push(x);
+        }
+
+        sets
+    }
+}
+
+#[test]
+fn test_union_find() {
+    let mut sets = RollbackDisjointSet::new(6);
+
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+
+    assert!(sets.same_set(0, 3));
+    assert!(!sets.same_set(0, 4));
+    assert_eq!(4, sets.set_size(2));
+    assert_eq!(3, sets.set_count());
+    assert_eq!(vec![vec![0, 1, 2, 3], vec![4], vec![5]], sets.sets());
+}
+
+#[test]
+fn test_undo() {
+    let mut sets = RollbackDisjointSet::new(4);
+
+    sets.union(0, 1);
+    sets.union(2, 3);
+    sets.union(1, 0);
+    sets.union(0, 3);
+
+    assert!(sets.undo());
+    assert_eq!(vec![vec![0, 1], vec![2, 3]], sets.sets());
+
+    // The union of elements already in the same set is undone too.
+    assert!(sets.undo());
+    assert_eq!(vec![vec![0, 1], vec![2, 3]], sets.sets());
+
+    assert!(sets.undo());
+    assert!(sets.undo());
+    assert!(!sets.undo());
+    assert_eq!(4, sets.set_count());
+    assert_eq!(1, sets.set_size(3));
+}
+
+#[test]
+fn test_rollback() {
+    let mut sets = RollbackDisjointSet::new(5);
+
+    sets.union(0, 1);
+    let snapshot = sets.snapshot();
+
+    sets.union(1, 2);
+    sets.union(3, 4);
+    sets.union(4, 0);
+    assert_eq!(1, sets.set_count());
+
+    sets.rollback(snapshot);
+
+    assert_eq!(vec![vec![0, 1], vec![2], vec![3], vec![4]], sets.sets());
+    assert_eq!(2, sets.set_size(0));
+}
+
+#[test]
+fn test_dynamic_connectivity() {
+    use super::DisjointSet;
+
+    // Edges of a path added in a divide and conquer over their indices, with every prefix of
+    // them checked against a disjoint set built from scratch.
+    fn check(sets: &mut RollbackDisjointSet, edges: &[(usize, usize)], added: usize) {
+        let mut expected = DisjointSet::new(sets.size());
+        for &(x, y) in &edges[..added] {
+            expected.union(x, y);
+        }
+
+        assert_eq!(expected.sets(), sets.sets());
+    }
+
+    fn divide(sets: &mut RollbackDisjointSet, edges: &[(usize, usize)], from: usize, to: usize) {
+        if to - from == 1 {
+            check(sets, edges, from);
+            return;
+        }
+
+        let middle = (from + to) / 2;
+        let snapshot = sets.snapshot();
+
+        divide(sets, edges, from, middle);
+
+        for &(x, y) in &edges[from..middle] {
+            sets.union(x, y);
+        }
+
+        divide(sets, edges, middle, to);
+        sets.rollback(snapshot);
+    }
+
+    let edges = vec![(3, 1), (0, 4), (1, 0), (6, 2), (4, 3), (5, 7), (2, 5), (7, 6)];
+    let mut sets = RollbackDisjointSet::new(8);
+
+    divide(&mut sets, &edges, 0, edges.len() + 1);
+
+    assert_eq!(8, sets.set_count());
+}
+
+#[test]
+#[should_panic]
+fn test_rollback_future() {
+    let mut sets = RollbackDisjointSet::new(2);
+
+    sets.union(0, 1);
+    sets.rollback(2);
+}
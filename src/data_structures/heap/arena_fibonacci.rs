@@ -0,0 +1,1176 @@
+// Based on http://www.keithschwarz.com/interesting/code/?dir=fibonacci-heap
+//
+// Entries live in a single Vec and refer to each other by index. Slots of removed entries
+// are kept in a free list threaded through `next` and are reused by later pushes.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::usize;
+use std::vec;
+
+use super::{PriorityQueue, MeldablePriorityQueue, AddressablePriorityQueue};
+use super::{Compare, MinOrder};
+
+const NONE: usize = usize::MAX;
+
+pub struct FibonacciHeap<T, C = MinOrder> {
+    id: usize,
+    size: usize,
+    min: usize,
+    free: usize,
+    entries: Vec<Entry<T>>,
+    tree_table: Vec<usize>,
+    to_visit: Vec<usize>,
+    compare: C,
+}
+
+#[derive(Clone)]
+struct Entry<T> {
+    pub value: Option<T>,
+    pub generation: usize,
+
+    pub degree: usize,
+    pub is_marked: bool,
+    pub parent: usize,
+    pub next: usize,
+    pub prev: usize,
+    pub child: usize,
+}
+
+/// Stable reference to a value pushed into a `FibonacciHeap`.
+///
+/// Slots are reused, so the handle also remembers the generation of its slot, and the heap it
+/// was returned by. Using a handle whose value was already popped, deleted or appended to
+/// another heap panics instead of touching the new occupant.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Handle {
+    heap: usize,
+    index: usize,
+    generation: usize,
+}
+
+impl<T: PartialOrd> FibonacciHeap<T> {
+    pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap::with_comparator(MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Create a heap that pops values in the order given by `compare`, smallest first.
+    pub fn with_comparator(compare: C) -> FibonacciHeap<T, C> {
+        FibonacciHeap {
+            id: new_id(),
+            min: NONE,
+            free: NONE,
+            size: 0,
+            entries: Vec::new(),
+            to_visit: Vec::new(),
+            tree_table: Vec::new(),
+            compare: compare,
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        if self.min == NONE {
+            None
+        } else {
+            Some(self.value(self.min))
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min == NONE
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) -> Handle {
+        let index = self.allocate(value);
+        let handle = Handle {
+            heap: self.id,
+            index: index,
+            generation: self.entries[index].generation,
+        };
+
+        let min = self.min;
+        self.min = self.merge_entries(min, index);
+        self.size += 1;
+
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.size -= 1;
+
+        let min = self.min;
+        self.min = NONE;
+
+        let min_next = self.entries[min].next;
+        if min_next != min {
+            let min_prev = self.entries[min].prev;
+            self.entries[min_prev].next = min_next;
+            self.entries[min_next].prev = min_prev;
+            self.min = min_next;
+        }
+
+        let min_child = self.entries[min].child;
+        if min_child != NONE {
+            let mut current = min_child;
+            while {
+                self.entries[current].parent = NONE;
+                current = self.entries[current].next;
+
+                current != min_child
+            } {}
+        }
+
+        let new_min = self.min;
+        self.min = self.merge_entries(new_min, min_child);
+
+        let value = self.release(min);
+
+        if self.size == 0 {
+            return value;
+        }
+
+        let mut to_visit = mem::replace(&mut self.to_visit, Vec::new());
+
+        let mut current = self.min;
+        while to_visit.is_empty() || to_visit[0] != current {
+            to_visit.push(current);
+            current = self.entries[current].next;
+        }
+
+        for &link_to_visit in &to_visit {
+            let mut link = link_to_visit;
+
+            loop {
+                let link_degree = self.entries[link].degree;
+
+                while link_degree >= self.tree_table.len() {
+                    self.tree_table.push(NONE);
+                }
+
+                let other = self.tree_table[link_degree];
+                if other == NONE {
+                    self.tree_table[link_degree] = link;
+                    break;
+                }
+
+                self.tree_table[link_degree] = NONE;
+
+                let (min_link, max) = if self.less(link, other) {
+                    (link, other)
+                } else {
+                    (other, link)
+                };
+
+                let max_next = self.entries[max].next;
+                let max_prev = self.entries[max].prev;
+                self.entries[max_next].prev = max_prev;
+                self.entries[max_prev].next = max_next;
+
+                self.entries[max].next = max;
+                self.entries[max].prev = max;
+                self.entries[max].parent = min_link;
+                self.entries[max].is_marked = false;
+
+                let min_link_child = self.entries[min_link].child;
+                self.entries[min_link].child = self.merge_entries(min_link_child, max);
+                self.entries[min_link].degree += 1;
+
+                link = min_link;
+            }
+
+            if !self.less(self.min, link) {
+                self.min = link;
+            }
+        }
+
+        to_visit.clear();
+        self.to_visit = to_visit;
+        self.tree_table.clear();
+
+        value
+    }
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap or `value` is greater than the current one.
+    pub fn decrease_key(&mut self, handle: &Handle, value: T) {
+        let index = self.index_of(handle, "decrease_key");
+
+        assert!(!self.compare.less(self.value(index), &value),
+                "Called decrease_key with a value greater than the current one");
+
+        self.entries[index].value = Some(value);
+
+        let parent = self.entries[index].parent;
+        if parent != NONE && self.less(index, parent) {
+            self.cut(index, parent);
+            self.cascading_cut(parent);
+        }
+
+        if self.less(index, self.min) {
+            self.min = index;
+        }
+    }
+
+    /// Remove the value referenced by `handle` from the heap and return it.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap.
+    pub fn delete(&mut self, handle: &Handle) -> T {
+        let index = self.index_of(handle, "delete");
+
+        let parent = self.entries[index].parent;
+        if parent != NONE {
+            self.cut(index, parent);
+            self.cascading_cut(parent);
+        }
+
+        // Pretend the entry is the smallest one, so pop removes it and consolidates the roots.
+        self.min = index;
+        self.pop().unwrap()
+    }
+
+    /// Merge two heaps into one, which keeps the ordering of `x`.
+    ///
+    /// See `append` for the cost and the handles.
+    pub fn merge(mut x: FibonacciHeap<T, C>, mut y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, which keeps its ordering, and leave `other`
+    /// empty.
+    ///
+    /// Entries of `other` are moved after the entries of this heap, because they live in one
+    /// vector, so this takes O(slots of `other`) time instead of the O(1) of the other
+    /// Fibonacci heaps. Merge the smaller heap into the larger one where possible.
+    ///
+    /// Handles returned by this heap stay valid, while handles returned by `other` are
+    /// invalidated: using them with either heap panics.
+    pub fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        let offset = self.entries.len();
+        let shift = |index: usize| if index == NONE { NONE } else { index + offset };
+
+        let other_min = shift(other.min);
+        let mut other_free = shift(other.free);
+
+        for entry in other.entries.drain(..) {
+            self.entries.push(Entry {
+                parent: shift(entry.parent),
+                next: shift(entry.next),
+                prev: shift(entry.prev),
+                child: shift(entry.child),
+                ..entry
+            });
+        }
+
+        while other_free != NONE {
+            let next = self.entries[other_free].next;
+            self.entries[other_free].next = self.free;
+            self.free = other_free;
+            other_free = next;
+        }
+
+        let min = self.min;
+        self.min = self.merge_entries(min, other_min);
+        self.size += other.size;
+
+        // Slots of `other` start over, so its old handles must not match them.
+        other.id = new_id();
+        other.min = NONE;
+        other.free = NONE;
+        other.size = 0;
+    }
+
+    /// Remove all values from the heap and return them from the smallest to the largest.
+    ///
+    /// Values are popped one by one, so the heap is left empty even if the iterator is
+    /// dropped early.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C> {
+        DrainSorted { heap: self }
+    }
+
+    /// Panic if the structure of the heap is broken: a value is smaller than its parent or
+    /// the minimum, an entry points at a wrong parent, a sibling list isn't a consistent ring,
+    /// a degree doesn't match the number of children, the number of entries isn't `size`, or
+    /// a slot is both used and free.
+    pub fn check_invariants(&self) {
+        let mut count = 0;
+        let mut rings = vec![(self.min, NONE)];
+
+        while let Some((start, parent)) = rings.pop() {
+            if start == NONE {
+                continue;
+            }
+
+            let mut current = start;
+            let mut ring_len = 0;
+
+            loop {
+                count += 1;
+                ring_len += 1;
+                assert!(count <= self.size,
+                        "Invariant violated: more entries than size = {}",
+                        self.size);
+
+                let entry = &self.entries[current];
+                assert!(entry.value.is_some(),
+                        "Invariant violated: free slot {} is linked into the forest",
+                        current);
+                assert!(entry.parent == parent,
+                        "Invariant violated: entry {} points at a wrong parent",
+                        current);
+                assert!(self.entries[entry.next].prev == current &&
+                        self.entries[entry.prev].next == current,
+                        "Invariant violated: broken sibling ring at entry {}",
+                        current);
+
+                if parent == NONE {
+                    assert!(!self.less(current, self.min),
+                            "Invariant violated: root {} is smaller than the minimum",
+                            current);
+                } else {
+                    assert!(!self.less(current, parent),
+                            "Invariant violated: entry {} is smaller than its parent",
+                            current);
+                }
+
+                if entry.child == NONE {
+                    assert!(entry.degree == 0,
+                            "Invariant violated: degree of entry {} without children isn't 0",
+                            current);
+                } else {
+                    rings.push((entry.child, current));
+                }
+
+                current = entry.next;
+                if current == start {
+                    break;
+                }
+            }
+
+            if parent != NONE {
+                assert!(self.entries[parent].degree == ring_len,
+                        "Invariant violated: degree = {} of entry {} with {} children",
+                        self.entries[parent].degree,
+                        parent,
+                        ring_len);
+            }
+        }
+
+        assert!(count == self.size,
+                "Invariant violated: {} entries, but size = {}",
+                count,
+                self.size);
+
+        let mut free = 0;
+        let mut current = self.free;
+
+        while current != NONE {
+            free += 1;
+            assert!(free + self.size <= self.entries.len(),
+                    "Invariant violated: free list has a cycle");
+            assert!(self.entries[current].value.is_none(),
+                    "Invariant violated: used slot {} is in the free list",
+                    current);
+
+            current = self.entries[current].next;
+        }
+
+        assert!(free + self.size == self.entries.len(),
+                "Invariant violated: {} free and {} used slots out of {}",
+                free,
+                self.size,
+                self.entries.len());
+    }
+
+    /// Consume the heap and return its values from the smallest to the largest.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.size);
+
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+
+        values
+    }
+
+    fn allocate(&mut self, value: T) -> usize {
+        if self.free == NONE {
+            let index = self.entries.len();
+            self.entries.push(Entry::new(value, index));
+
+            return index;
+        }
+
+        let index = self.free;
+        let entry = &mut self.entries[index];
+        self.free = entry.next;
+
+        entry.value = Some(value);
+        entry.degree = 0;
+        entry.is_marked = false;
+        entry.parent = NONE;
+        entry.next = index;
+        entry.prev = index;
+        entry.child = NONE;
+
+        index
+    }
+
+    fn index_of(&self, handle: &Handle, method: &str) -> usize {
+        match self.entries.get(handle.index) {
+            Some(entry) if handle.heap == self.id && entry.generation == handle.generation &&
+                           entry.value.is_some() => handle.index,
+            _ => {
+                panic!("Called {} with a handle to a value that is no longer in the heap",
+                       method)
+            }
+        }
+    }
+
+    #[inline]
+    fn value(&self, index: usize) -> &T {
+        match self.entries[index].value {
+            Some(ref value) => value,
+            None => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn less(&self, x: usize, y: usize) -> bool {
+        self.compare.less(self.value(x), self.value(y))
+    }
+
+    fn cut(&mut self, index: usize, parent: usize) {
+        let next = self.entries[index].next;
+
+        if next == index {
+            self.entries[parent].child = NONE;
+        } else {
+            let prev = self.entries[index].prev;
+            self.entries[prev].next = next;
+            self.entries[next].prev = prev;
+
+            if self.entries[parent].child == index {
+                self.entries[parent].child = next;
+            }
+
+            self.entries[index].next = index;
+            self.entries[index].prev = index;
+        }
+
+        self.entries[parent].degree -= 1;
+        self.entries[index].parent = NONE;
+        self.entries[index].is_marked = false;
+
+        let min = self.min;
+        self.min = self.merge_entries(min, index);
+    }
+
+    fn cascading_cut(&mut self, mut index: usize) {
+        loop {
+            let parent = self.entries[index].parent;
+            if parent == NONE {
+                break;
+            }
+
+            if !self.entries[index].is_marked {
+                self.entries[index].is_marked = true;
+                break;
+            }
+
+            self.cut(index, parent);
+            index = parent;
+        }
+    }
+
+    fn merge_entries(&mut self, x: usize, y: usize) -> usize {
+        if x == NONE {
+            y
+        } else if y == NONE {
+            x
+        } else {
+            let x_next = self.entries[x].next;
+            let y_next = self.entries[y].next;
+            self.entries[x].next = y_next;
+            self.entries[y_next].prev = x;
+            self.entries[y].next = x_next;
+            self.entries[x_next].prev = y;
+
+            if self.less(x, y) {
+                x
+            } else {
+                y
+            }
+        }
+    }
+}
+
+impl<T, C> FibonacciHeap<T, C> {
+    /// Return an iterator over the values of the heap in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            entries: self.entries.iter(),
+            len: self.size,
+        }
+    }
+
+    /// Render the forest as a Graphviz digraph with an edge from every entry to each of its
+    /// children. Nodes are named after the slots of the entries. The minimum has a double
+    /// border and marked entries are dashed.
+    pub fn to_dot(&self) -> String
+        where T: fmt::Debug
+    {
+        let mut dot = String::from("digraph {\n");
+        let mut rings = vec![self.min];
+
+        while let Some(start) = rings.pop() {
+            if start == NONE {
+                continue;
+            }
+
+            let mut current = start;
+
+            loop {
+                let entry = &self.entries[current];
+                let label = format!("{:?}", entry.value.as_ref().unwrap())
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"");
+                let style = if current == self.min {
+                    ", peripheries=2"
+                } else if entry.is_marked {
+                    ", style=dashed"
+                } else {
+                    ""
+                };
+
+                dot.push_str(&format!("    n{} [label=\"{}\"{}];\n", current, label, style));
+                if entry.parent != NONE {
+                    dot.push_str(&format!("    n{} -> n{};\n", entry.parent, current));
+                }
+
+                rings.push(entry.child);
+
+                current = entry.next;
+                if current == start {
+                    break;
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Remove all values from the heap. Their slots are kept for later pushes.
+    pub fn clear(&mut self) {
+        for index in 0..self.entries.len() {
+            if self.entries[index].value.is_some() {
+                self.release(index);
+            }
+        }
+
+        self.min = NONE;
+        self.size = 0;
+    }
+
+    fn release(&mut self, index: usize) -> Option<T> {
+        let entry = &mut self.entries[index];
+        entry.generation = entry.generation.wrapping_add(1);
+        entry.next = self.free;
+        self.free = index;
+
+        entry.value.take()
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for FibonacciHeap<T, C> {
+    fn default() -> FibonacciHeap<T, C> {
+        FibonacciHeap::with_comparator(C::default())
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for FibonacciHeap<T, C> {
+    /// Copy the heap with the same slots, so handles to the values of the original heap refer
+    /// to the same values of the copy.
+    fn clone(&self) -> FibonacciHeap<T, C> {
+        FibonacciHeap {
+            id: self.id,
+            size: self.size,
+            min: self.min,
+            free: self.free,
+            entries: self.entries.clone(),
+            tree_table: Vec::new(),
+            to_visit: Vec::new(),
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for FibonacciHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for FibonacciHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> FibonacciHeap<T, C> {
+        let mut heap = FibonacciHeap::default();
+        heap.extend(iter);
+
+        heap
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, C> IntoIterator for FibonacciHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consume the heap and return an iterator over its values in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        let values: Vec<_> = self.entries.into_iter().filter_map(|entry| entry.value).collect();
+
+        IntoIter { values: values.into_iter() }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a FibonacciHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over borrowed values of a `FibonacciHeap` in arbitrary order.
+pub struct Iter<'a, T: 'a> {
+    entries: slice::Iter<'a, Entry<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        for entry in &mut self.entries {
+            if let Some(ref value) = entry.value {
+                self.len -= 1;
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Iterator over the values of a consumed `FibonacciHeap` in arbitrary order.
+pub struct IntoIter<T> {
+    values: vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.values.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Iterator popping the values of a `FibonacciHeap` from the smallest to the largest.
+pub struct DrainSorted<'a, T: 'a, C: 'a> {
+    heap: &'a mut FibonacciHeap<T, C>,
+}
+
+impl<'a, T, C: Compare<T>> Iterator for DrainSorted<'a, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.size, Some(self.heap.size))
+    }
+}
+
+impl<'a, T, C: Compare<T>> ExactSizeIterator for DrainSorted<'a, T, C> {}
+
+impl<'a, T, C> Drop for DrainSorted<'a, T, C> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for FibonacciHeap<T, C> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        FibonacciHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        FibonacciHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        FibonacciHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        FibonacciHeap::is_empty(self)
+    }
+}
+
+impl<T, C: Compare<T>> MeldablePriorityQueue<T> for FibonacciHeap<T, C> {
+    fn merge(x: FibonacciHeap<T, C>, y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        FibonacciHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        FibonacciHeap::append(self, other)
+    }
+}
+
+impl<T, C: Compare<T>> AddressablePriorityQueue<T> for FibonacciHeap<T, C> {
+    type Handle = Handle;
+
+    fn push_with_handle(&mut self, value: T) -> Handle {
+        FibonacciHeap::push(self, value)
+    }
+
+    fn decrease_key(&mut self, handle: &Handle, value: T) {
+        FibonacciHeap::decrease_key(self, handle, value)
+    }
+
+    fn delete(&mut self, handle: &Handle) -> T {
+        FibonacciHeap::delete(self, handle)
+    }
+}
+
+/// Return an id which no other heap has, for the handles to tell heaps apart.
+fn new_id() -> usize {
+    static HEAPS: AtomicUsize = AtomicUsize::new(0);
+
+    HEAPS.fetch_add(1, Ordering::Relaxed)
+}
+
+impl<T> Entry<T> {
+    pub fn new(value: T, index: usize) -> Entry<T> {
+        Entry {
+            value: Some(value),
+            generation: 0,
+
+            degree: 0,
+            is_marked: false,
+            parent: NONE,
+            next: index,
+            prev: index,
+            child: NONE,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = FibonacciHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size);
+}
+
+#[test]
+fn test_min() {
+    let mut heap = FibonacciHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_pop() {
+    let mut heap = FibonacciHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.pop().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = FibonacciHeap::new();
+
+    heap.push(7);
+    heap.push(1);
+    heap.push(8);
+    heap.push(4);
+    heap.push(5);
+    heap.push(2);
+    heap.push(3);
+    heap.push(6);
+
+    assert_eq!(1, heap.pop().unwrap());
+    assert_eq!(2, heap.pop().unwrap());
+    assert_eq!(3, heap.pop().unwrap());
+    assert_eq!(4, heap.pop().unwrap());
+    assert_eq!(5, heap.pop().unwrap());
+    assert_eq!(6, heap.pop().unwrap());
+    assert_eq!(7, heap.pop().unwrap());
+    assert_eq!(8, heap.pop().unwrap());
+}
+
+#[test]
+fn test_reuse_slots() {
+    let mut heap = FibonacciHeap::new();
+
+    for i in 0..10 {
+        heap.push(i);
+    }
+
+    for _ in 0..10 {
+        heap.pop();
+    }
+
+    for i in 0..10 {
+        heap.push(i);
+    }
+
+    assert_eq!(10, heap.entries.len());
+}
+
+#[test]
+fn test_decrease_key() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i * 10)).collect();
+
+    // Consolidate, so the decreased entries have parents and cascading cuts happen.
+    assert_eq!(0, heap.pop().unwrap());
+
+    heap.decrease_key(&handles[9], 5);
+    heap.decrease_key(&handles[7], 15);
+    heap.decrease_key(&handles[8], 1);
+    heap.decrease_key(&handles[6], 16);
+
+    assert_eq!(1, *heap.min().unwrap());
+    assert_eq!(vec![1, 5, 10, 15, 16, 20, 30, 40, 50],
+               (0..9).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_decrease_key_reused_slot() {
+    let mut heap = FibonacciHeap::new();
+
+    let handle = heap.push(1);
+    heap.pop();
+    heap.push(2);
+
+    heap.decrease_key(&handle, 0);
+}
+
+#[test]
+fn test_delete() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+    assert_eq!(5, heap.delete(&handles[5]));
+    assert_eq!(1, heap.delete(&handles[1]));
+    assert_eq!(9, heap.delete(&handles[9]));
+
+    assert_eq!(6, heap.size());
+    assert_eq!(vec![2, 3, 4, 6, 7, 8],
+               (0..6).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = FibonacciHeap::new();
+    let mut y = FibonacciHeap::new();
+
+    let handle = x.push(10);
+    for i in 0..5 {
+        x.push(i * 2);
+        y.push(i * 2 + 1);
+    }
+
+    // Leave free slots in both heaps.
+    x.pop();
+    y.pop();
+
+    let mut merged = FibonacciHeap::merge(x, y);
+    merged.decrease_key(&handle, 0);
+
+    assert_eq!(9, merged.size());
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9],
+               (0..9).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_append() {
+    let mut heap: FibonacciHeap<_> = (0..5).map(|i| i * 2).collect();
+    let mut other = FibonacciHeap::new();
+
+    other.push(9);
+    other.extend((0..4).map(|i| i * 2 + 1));
+    other.pop();
+
+    heap.append(&mut other);
+    assert!(other.is_empty());
+    assert!(other.entries.is_empty());
+    assert_eq!(9, heap.size());
+
+    other.push(1);
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9], heap.into_sorted_vec());
+    assert_eq!(vec![1], other.into_sorted_vec());
+}
+
+#[test]
+#[should_panic]
+fn test_append_stale_handle() {
+    let mut heap = FibonacciHeap::new();
+    let mut other = FibonacciHeap::new();
+
+    let handle = other.push(1);
+    heap.append(&mut other);
+    other.push(2);
+
+    other.decrease_key(&handle, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_append_handle_of_other() {
+    let mut heap = FibonacciHeap::new();
+    let mut other = FibonacciHeap::new();
+
+    // Both handles have the index and generation of the first slot.
+    heap.push(5);
+    let handle = other.push(7);
+    heap.append(&mut other);
+
+    heap.decrease_key(&handle, 0);
+}
+
+#[test]
+fn test_max_order() {
+    use super::MaxOrder;
+
+    let mut heap = FibonacciHeap::with_comparator(MaxOrder);
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(8, *heap.min().unwrap());
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_comparator() {
+    use super::KeyOrder;
+
+    let mut by_key = FibonacciHeap::with_comparator(KeyOrder::new(|x: &(&str, i32)| x.1));
+    let mut by_closure = FibonacciHeap::with_comparator(|x: &(&str, i32), y: &(&str, i32)| x.0.cmp(y.0));
+
+    for &value in &[("b", 3), ("c", 1), ("a", 2)] {
+        by_key.push(value);
+        by_closure.push(value);
+    }
+
+    assert_eq!(vec!["c", "a", "b"], (0..3).map(|_| by_key.pop().unwrap().0).collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b", "c"],
+               (0..3).map(|_| by_closure.pop().unwrap().0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut heap = FibonacciHeap::new();
+
+        for i in 0..100 {
+            heap.push(DropCounter::new(i, &drops));
+        }
+
+        heap.pop();
+        assert_eq!(1, drops.get());
+    }
+
+    assert_eq!(100, drops.get());
+}
+
+#[test]
+fn test_iter() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+    heap.extend(vec![20, 10]);
+
+    let mut values: Vec<_> = heap.iter().cloned().collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+    assert_eq!(11, heap.iter().len());
+
+    let mut values: Vec<_> = heap.into_iter().collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+}
+
+#[test]
+fn test_into_sorted_vec() {
+    let heap: FibonacciHeap<_> = vec![7, 1, 8, 4, 5, 2, 3, 6].into_iter().collect();
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], heap.into_sorted_vec());
+}
+
+#[test]
+fn test_drain_sorted() {
+    let mut heap: FibonacciHeap<_> = (0..10).rev().collect();
+
+    assert_eq!(vec![0, 1, 2], heap.drain_sorted().take(3).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+
+    heap.push(1);
+    assert_eq!(vec![1], heap.drain_sorted().collect::<Vec<_>>());
+    assert_eq!(10, heap.entries.len());
+}
+
+#[test]
+fn test_clone() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    let handle = heap.push(15);
+    heap.pop();
+
+    let mut copy = heap.clone();
+    heap.decrease_key(&handle, 0);
+    copy.decrease_key(&handle, 5);
+
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9], heap.into_sorted_vec());
+    assert_eq!(vec![1, 2, 3, 4, 5, 5, 6, 7, 8, 9], copy.into_sorted_vec());
+}
+
+#[test]
+fn test_debug() {
+    let heap: FibonacciHeap<_> = Some(1).into_iter().collect();
+
+    assert_eq!("[1]", format!("{:?}", heap));
+}
+
+#[test]
+fn test_check_invariants() {
+    let mut heap = FibonacciHeap::new();
+    heap.check_invariants();
+
+    let handles: Vec<_> = (0..50).map(|i| heap.push((i * 37) % 50 + 100)).collect();
+    heap.check_invariants();
+
+    heap.pop();
+    heap.check_invariants();
+
+    for (i, handle) in handles.iter().enumerate().skip(1).filter(|&(i, _)| i % 3 == 0) {
+        heap.decrease_key(handle, i as i32);
+        heap.check_invariants();
+    }
+
+    for handle in handles.iter().skip(1).take(20) {
+        heap.delete(handle);
+        heap.check_invariants();
+    }
+
+    heap.extend(0..10);
+    heap.check_invariants();
+
+    while heap.pop().is_some() {
+        heap.check_invariants();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_check_invariants_broken() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+
+    heap.free = NONE;
+    heap.check_invariants();
+}
+
+#[test]
+fn test_to_dot() {
+    let mut heap = FibonacciHeap::new();
+
+    for &i in &[3, 1, 2] {
+        heap.push(i);
+    }
+
+    heap.pop();
+
+    assert_eq!("digraph {\n    n2 [label=\"2\", peripheries=2];\n    n0 [label=\"3\"];\n    \
+                n2 -> n0;\n}\n",
+               heap.to_dot());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = FibonacciHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
@@ -0,0 +1,130 @@
+// Min-heap on top of std::collections::BinaryHeap, so the standard heap can be used
+// wherever the heaps of this module are expected.
+
+use std::cmp::Reverse;
+use std::collections;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct BinaryHeap<T: Ord> {
+    heap: collections::BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    pub fn new() -> BinaryHeap<T> {
+        BinaryHeap { heap: collections::BinaryHeap::new() }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.heap.push(Reverse(value));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|entry| entry.0)
+    }
+
+    pub fn merge(mut x: BinaryHeap<T>, mut y: BinaryHeap<T>) -> BinaryHeap<T> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, leaving `other` empty.
+    pub fn append(&mut self, other: &mut BinaryHeap<T>) {
+        self.heap.append(&mut other.heap);
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> BinaryHeap<T> {
+        BinaryHeap::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for BinaryHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        BinaryHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        BinaryHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        BinaryHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinaryHeap::is_empty(self)
+    }
+}
+
+impl<T: Ord> MeldablePriorityQueue<T> for BinaryHeap<T> {
+    fn merge(x: BinaryHeap<T>, y: BinaryHeap<T>) -> BinaryHeap<T> {
+        BinaryHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut BinaryHeap<T>) {
+        BinaryHeap::append(self, other)
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = BinaryHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let mut heap = BinaryHeap::new();
+
+    heap.push(2);
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = BinaryHeap::new();
+
+    heap.push(7);
+    heap.push(1);
+    heap.push(8);
+    heap.push(4);
+    heap.push(5);
+    heap.push(2);
+    heap.push(3);
+    heap.push(6);
+
+    assert_eq!(1, heap.pop().unwrap());
+    assert_eq!(2, heap.pop().unwrap());
+    assert_eq!(3, heap.pop().unwrap());
+    assert_eq!(4, heap.pop().unwrap());
+    assert_eq!(5, heap.pop().unwrap());
+    assert_eq!(6, heap.pop().unwrap());
+    assert_eq!(7, heap.pop().unwrap());
+    assert_eq!(8, heap.pop().unwrap());
+}
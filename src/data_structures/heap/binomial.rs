@@ -0,0 +1,313 @@
+// Based on "A Data Structure for Manipulating Priority Queues" by Jean Vuillemin.
+//
+// The heap is a list of binomial trees indexed by their rank, so melding two heaps works
+// like adding two binary numbers and takes O(log n) time in the worst case.
+
+use std::mem;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct BinomialHeap<T: PartialOrd> {
+    size: usize,
+    min: Option<usize>,
+    trees: Vec<Option<Box<Node<T>>>>,
+}
+
+struct Node<T: PartialOrd> {
+    pub value: T,
+    // Child at index `i` is a binomial tree of rank `i`.
+    pub children: Vec<Box<Node<T>>>,
+}
+
+impl<T: PartialOrd> BinomialHeap<T> {
+    pub fn new() -> BinomialHeap<T> {
+        BinomialHeap {
+            size: 0,
+            min: None,
+            trees: Vec::new(),
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.min.map(|rank| &self.tree(rank).value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) {
+        let mut carry = Box::new(Node::new(value));
+        let mut rank = 0;
+
+        loop {
+            if rank == self.trees.len() {
+                self.trees.push(None);
+            }
+
+            match self.trees[rank].take() {
+                None => break,
+                Some(tree) => {
+                    carry = Node::link(carry, tree);
+                    rank += 1;
+                }
+            }
+        }
+
+        self.trees[rank] = Some(carry);
+        self.size += 1;
+
+        // Trees below `rank` were linked into the new tree, which keeps the smallest of their
+        // roots. Trees above it are left untouched.
+        self.min = match self.min {
+            Some(min) if min > rank && !(self.tree(rank).value < self.tree(min).value) => Some(min),
+            _ => Some(rank),
+        };
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let min = match self.min {
+            None => return None,
+            Some(min) => min,
+        };
+
+        let node = *self.trees[min].take().unwrap();
+        self.size -= 1;
+
+        self.add_trees(node.children.into_iter().map(Some).collect());
+
+        while let Some(&None) = self.trees.last() {
+            self.trees.pop();
+        }
+
+        self.update_min();
+
+        Some(node.value)
+    }
+
+    pub fn merge(mut x: BinomialHeap<T>, mut y: BinomialHeap<T>) -> BinomialHeap<T> {
+        let y_trees = mem::replace(&mut y.trees, Vec::new());
+
+        x.add_trees(y_trees);
+        x.size += y.size;
+        x.update_min();
+
+        x
+    }
+
+    /// Add trees of another heap, carrying linked trees to the next rank.
+    fn add_trees(&mut self, trees: Vec<Option<Box<Node<T>>>>) {
+        let mut trees = trees.into_iter();
+        let mut carry = None;
+        let mut rank = 0;
+
+        loop {
+            let next = trees.next();
+            if next.is_none() && carry.is_none() {
+                break;
+            }
+
+            if rank == self.trees.len() {
+                self.trees.push(None);
+            }
+
+            let current = self.trees[rank].take();
+            let (sum, next_carry) = match (current, next.and_then(|tree| tree), carry.take()) {
+                (None, None, None) => (None, None),
+                (Some(x), None, None) | (None, Some(x), None) | (None, None, Some(x)) => {
+                    (Some(x), None)
+                }
+                (Some(x), Some(y), None) | (Some(x), None, Some(y)) | (None, Some(x), Some(y)) => {
+                    (None, Some(Node::link(x, y)))
+                }
+                (Some(x), Some(y), Some(z)) => (Some(x), Some(Node::link(y, z))),
+            };
+
+            self.trees[rank] = sum;
+            carry = next_carry;
+            rank += 1;
+        }
+    }
+
+    fn update_min(&mut self) {
+        self.min = None;
+
+        for rank in 0..self.trees.len() {
+            if let Some(ref tree) = self.trees[rank] {
+                match self.min {
+                    Some(min) if !(tree.value < self.tree(min).value) => {}
+                    _ => self.min = Some(rank),
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn tree(&self, rank: usize) -> &Node<T> {
+        match self.trees[rank] {
+            Some(ref tree) => tree,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<T: PartialOrd> Default for BinomialHeap<T> {
+    fn default() -> BinomialHeap<T> {
+        BinomialHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for BinomialHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        BinomialHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinomialHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        BinomialHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        BinomialHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinomialHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> MeldablePriorityQueue<T> for BinomialHeap<T> {
+    fn merge(x: BinomialHeap<T>, y: BinomialHeap<T>) -> BinomialHeap<T> {
+        BinomialHeap::merge(x, y)
+    }
+}
+
+impl<T: PartialOrd> Node<T> {
+    pub fn new(value: T) -> Node<T> {
+        Node {
+            value: value,
+            children: Vec::new(),
+        }
+    }
+
+    /// Link two trees of the same rank into one tree of the next rank.
+    pub fn link(mut x: Box<Node<T>>, mut y: Box<Node<T>>) -> Box<Node<T>> {
+        if y.value < x.value {
+            y.children.push(x);
+            y
+        } else {
+            x.children.push(y);
+            x
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size);
+}
+
+#[test]
+fn test_min() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_pop() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.pop().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(7);
+    heap.push(1);
+    heap.push(8);
+    heap.push(4);
+    heap.push(5);
+    heap.push(2);
+    heap.push(3);
+    heap.push(6);
+
+    assert_eq!(1, heap.pop().unwrap());
+    assert_eq!(2, heap.pop().unwrap());
+    assert_eq!(3, heap.pop().unwrap());
+    assert_eq!(4, heap.pop().unwrap());
+    assert_eq!(5, heap.pop().unwrap());
+    assert_eq!(6, heap.pop().unwrap());
+    assert_eq!(7, heap.pop().unwrap());
+    assert_eq!(8, heap.pop().unwrap());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = BinomialHeap::new();
+    let mut y = BinomialHeap::new();
+
+    for i in 0..7 {
+        x.push(i * 2);
+    }
+
+    for i in 0..5 {
+        y.push(i * 2 + 1);
+    }
+
+    let mut merged = BinomialHeap::merge(x, y);
+
+    assert_eq!(12, merged.size());
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12],
+               (0..12).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_trees() {
+    let mut heap = BinomialHeap::new();
+
+    for i in 0..11 {
+        heap.push(i);
+    }
+
+    // 11 = 0b1011
+    let ranks: Vec<_> = heap.trees
+        .iter()
+        .map(|tree| tree.as_ref().map(|tree| tree.children.len()))
+        .collect();
+    assert_eq!(vec![Some(0), Some(1), None, Some(3)], ranks);
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = BinomialHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
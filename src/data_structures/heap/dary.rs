@@ -0,0 +1,431 @@
+// Implicit heap stored in a Vec where every entry has up to `arity` children. Children of
+// the entry at index `i` are at indices `arity * i + 1` to `arity * i + arity`.
+//
+// Every pushed value gets an id, and `positions` maps ids to indices in the heap, so values
+// can be found again by their handles. Ids of removed values are reused.
+
+use std::usize;
+
+use super::{PriorityQueue, AddressablePriorityQueue};
+
+const NONE: usize = usize::MAX;
+const DEFAULT_ARITY: usize = 4;
+
+pub struct DaryHeap<T: PartialOrd> {
+    arity: usize,
+    entries: Vec<Entry<T>>,
+    positions: Vec<usize>,
+    generations: Vec<usize>,
+    free_ids: Vec<usize>,
+}
+
+struct Entry<T: PartialOrd> {
+    pub value: T,
+    pub id: usize,
+}
+
+/// Stable reference to a value pushed into a `DaryHeap`.
+///
+/// Ids are reused, so the handle also remembers the generation of its id. Using a handle
+/// whose value was already popped or deleted panics instead of touching the new owner of the id.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Handle {
+    id: usize,
+    generation: usize,
+}
+
+impl<T: PartialOrd> DaryHeap<T> {
+    /// Create a 4-ary heap.
+    pub fn new() -> DaryHeap<T> {
+        DaryHeap::with_arity(DEFAULT_ARITY)
+    }
+
+    /// Create a heap where every entry has up to `arity` children.
+    ///
+    /// ##Panics
+    /// If `arity` is less than 2.
+    pub fn with_arity(arity: usize) -> DaryHeap<T> {
+        assert!(arity >= 2, "Called with_arity with arity = {}", arity);
+
+        DaryHeap {
+            arity: arity,
+            entries: Vec::new(),
+            positions: Vec::new(),
+            generations: Vec::new(),
+            free_ids: Vec::new(),
+        }
+    }
+
+    /// Build a 4-ary heap from `values` in O(n) time.
+    pub fn from_vec(values: Vec<T>) -> DaryHeap<T> {
+        DaryHeap::from_vec_with_arity(values, DEFAULT_ARITY)
+    }
+
+    /// Build a heap where every entry has up to `arity` children from `values` in O(n) time.
+    ///
+    /// ##Panics
+    /// If `arity` is less than 2.
+    pub fn from_vec_with_arity(values: Vec<T>, arity: usize) -> DaryHeap<T> {
+        let mut heap = DaryHeap::with_arity(arity);
+        let len = values.len();
+
+        heap.entries = values.into_iter()
+            .enumerate()
+            .map(|(id, value)| Entry { value: value, id: id })
+            .collect();
+        heap.positions = (0..len).collect();
+        heap.generations = vec![0; len];
+
+        if len > 1 {
+            let mut start = heap.parent(len - 1);
+
+            loop {
+                heap.sift_down(start);
+
+                if start == 0 {
+                    break;
+                }
+
+                start -= 1;
+            }
+        }
+
+        heap
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.entries.first().map(|entry| &entry.value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn push(&mut self, value: T) -> Handle {
+        let index = self.entries.len();
+
+        let id = match self.free_ids.pop() {
+            Some(id) => {
+                self.positions[id] = index;
+                id
+            }
+            None => {
+                self.positions.push(index);
+                self.generations.push(0);
+                self.positions.len() - 1
+            }
+        };
+
+        self.entries.push(Entry {
+            value: value,
+            id: id,
+        });
+        self.sift_up(index);
+
+        Handle {
+            id: id,
+            generation: self.generations[id],
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.remove(0))
+        }
+    }
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap or `value` is greater than the current one.
+    pub fn decrease_key(&mut self, handle: &Handle, value: T) {
+        let index = self.index_of(handle, "decrease_key");
+
+        assert!(!(value > self.entries[index].value),
+                "Called decrease_key with a value greater than the current one");
+
+        self.entries[index].value = value;
+        self.sift_up(index);
+    }
+
+    /// Remove the value referenced by `handle` from the heap and return it.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap.
+    pub fn delete(&mut self, handle: &Handle) -> T {
+        let index = self.index_of(handle, "delete");
+
+        self.remove(index)
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        let last = self.entries.len() - 1;
+        self.swap(index, last);
+
+        let entry = self.entries.pop().unwrap();
+        self.positions[entry.id] = NONE;
+        self.generations[entry.id] = self.generations[entry.id].wrapping_add(1);
+        self.free_ids.push(entry.id);
+
+        if index < last {
+            let index = self.sift_up(index);
+            self.sift_down(index);
+        }
+
+        entry.value
+    }
+
+    fn index_of(&self, handle: &Handle, method: &str) -> usize {
+        match self.generations.get(handle.id) {
+            Some(&generation) if generation == handle.generation => self.positions[handle.id],
+            _ => {
+                panic!("Called {} with a handle to a value that is no longer in the heap",
+                       method)
+            }
+        }
+    }
+
+    /// Move the entry at `index` up until its parent is not greater and return its new index.
+    fn sift_up(&mut self, mut index: usize) -> usize {
+        while index > 0 {
+            let parent = self.parent(index);
+
+            if self.entries[index].value < self.entries[parent].value {
+                self.swap(index, parent);
+                index = parent;
+            } else {
+                break;
+            }
+        }
+
+        index
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.entries.len();
+
+        loop {
+            let first_child = self.arity * index + 1;
+            if first_child >= len {
+                break;
+            }
+
+            let last_child = if len - first_child < self.arity {
+                len
+            } else {
+                first_child + self.arity
+            };
+
+            let mut min_child = first_child;
+            for child in (first_child + 1)..last_child {
+                if self.entries[child].value < self.entries[min_child].value {
+                    min_child = child;
+                }
+            }
+
+            if self.entries[min_child].value < self.entries[index].value {
+                self.swap(index, min_child);
+                index = min_child;
+            } else {
+                break;
+            }
+        }
+    }
+
+    #[inline]
+    fn swap(&mut self, x: usize, y: usize) {
+        self.entries.swap(x, y);
+        self.positions[self.entries[x].id] = x;
+        self.positions[self.entries[y].id] = y;
+    }
+
+    #[inline]
+    fn parent(&self, index: usize) -> usize {
+        (index - 1) / self.arity
+    }
+}
+
+impl<T: PartialOrd> Default for DaryHeap<T> {
+    fn default() -> DaryHeap<T> {
+        DaryHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for DaryHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        DaryHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        DaryHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        DaryHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        DaryHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        DaryHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> AddressablePriorityQueue<T> for DaryHeap<T> {
+    type Handle = Handle;
+
+    fn push_with_handle(&mut self, value: T) -> Handle {
+        DaryHeap::push(self, value)
+    }
+
+    fn decrease_key(&mut self, handle: &Handle, value: T) {
+        DaryHeap::decrease_key(self, handle, value)
+    }
+
+    fn delete(&mut self, handle: &Handle) -> T {
+        DaryHeap::delete(self, handle)
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = DaryHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let mut heap = DaryHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_pop() {
+    let mut heap = DaryHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.pop().unwrap());
+}
+
+#[test]
+fn test_order() {
+    for arity in 2..6 {
+        let mut heap = DaryHeap::with_arity(arity);
+
+        heap.push(7);
+        heap.push(1);
+        heap.push(8);
+        heap.push(4);
+        heap.push(5);
+        heap.push(2);
+        heap.push(3);
+        heap.push(6);
+
+        assert_eq!(1, heap.pop().unwrap());
+        assert_eq!(2, heap.pop().unwrap());
+        assert_eq!(3, heap.pop().unwrap());
+        assert_eq!(4, heap.pop().unwrap());
+        assert_eq!(5, heap.pop().unwrap());
+        assert_eq!(6, heap.pop().unwrap());
+        assert_eq!(7, heap.pop().unwrap());
+        assert_eq!(8, heap.pop().unwrap());
+    }
+}
+
+#[test]
+fn test_from_vec() {
+    let mut heap = DaryHeap::from_vec_with_arity((0..20).rev().collect(), 3);
+
+    assert_eq!(20, heap.size());
+    assert_eq!((0..20).collect::<Vec<_>>(),
+               (0..20).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn test_arity() {
+    DaryHeap::<i32>::with_arity(1);
+}
+
+#[test]
+fn test_decrease_key() {
+    let mut heap = DaryHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i * 10)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+
+    heap.decrease_key(&handles[9], 5);
+    heap.decrease_key(&handles[7], 15);
+    heap.decrease_key(&handles[8], 1);
+    heap.decrease_key(&handles[6], 16);
+
+    assert_eq!(1, *heap.min().unwrap());
+    assert_eq!(vec![1, 5, 10, 15, 16, 20, 30, 40, 50],
+               (0..9).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_decrease_key_popped() {
+    let mut heap = DaryHeap::new();
+
+    let handle = heap.push(1);
+    heap.pop();
+    heap.push(2);
+
+    heap.decrease_key(&handle, 0);
+}
+
+#[test]
+fn test_delete() {
+    let mut heap = DaryHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+    assert_eq!(5, heap.delete(&handles[5]));
+    assert_eq!(1, heap.delete(&handles[1]));
+    assert_eq!(9, heap.delete(&handles[9]));
+
+    assert_eq!(6, heap.size());
+    assert_eq!(vec![2, 3, 4, 6, 7, 8],
+               (0..6).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = DaryHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
@@ -1,45 +1,77 @@
 // Based on http://www.keithschwarz.com/interesting/code/?dir=fibonacci-heap
 
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::mem;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::cell::{RefCell, Ref};
-use std::cmp::Ordering;
+use std::vec;
 
-pub struct FibonacciHeap<T: PartialOrd> {
+use super::{PriorityQueue, MeldablePriorityQueue, AddressablePriorityQueue};
+use super::{Compare, MinOrder};
+
+pub struct FibonacciHeap<T, C = MinOrder> {
+    identity: Rc<Identity>,
     size: usize,
     min: Link<T>,
     tree_table: Vec<Link<T>>,
     to_visit: Vec<Link<T>>,
+    compare: C,
 }
 
-struct Entry<T: PartialOrd> {
+struct Entry<T> {
     pub value: T,
 
     pub degree: usize,
-    // TODO: Implement decrease_key and delete
-    // pub is_marked: bool,
+    pub is_marked: bool,
     pub parent: Link<T>,
     pub next: Link<T>,
     pub prev: Link<T>,
     pub child: Link<T>,
 }
 
-enum Link<T: PartialOrd> {
+enum Link<T> {
     None,
     Some(Rc<RefCell<Entry<T>>>),
 }
 
+/// Identity of a heap, which handles are checked against. Appending a heap points its identity
+/// at the one of the heap it's appended to, so its handles follow their values.
+struct Identity {
+    parent: RefCell<Option<Rc<Identity>>>,
+}
+
+/// Stable reference to a value pushed into a `FibonacciHeap`.
+///
+/// A handle does not keep its entry alive: once the value has been popped or deleted,
+/// using the handle panics. So does using it with a heap that doesn't hold its value.
+pub struct Handle<T> {
+    entry: Weak<RefCell<Entry<T>>>,
+    heap: Rc<Identity>,
+}
+
 impl<T: PartialOrd> FibonacciHeap<T> {
     pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap::with_comparator(MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Create a heap that pops values in the order given by `compare`, smallest first.
+    pub fn with_comparator(compare: C) -> FibonacciHeap<T, C> {
         FibonacciHeap {
+            identity: Identity::new(),
             min: Link::None,
             size: 0,
             to_visit: Vec::new(),
             tree_table: Vec::new(),
+            compare: compare,
         }
     }
 
-    pub fn min(&self) -> Option<Ref<T>> {
+    pub fn min(&self) -> Option<Ref<'_, T>> {
         self.min.borrow()
     }
 
@@ -51,14 +83,59 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         self.size
     }
 
-    pub fn push(&mut self, value: T) {
+    pub fn push(&mut self, value: T) -> Handle<T> {
         let link = Link::new(value);
+        let handle = link.handle(&self.identity);
 
         let mut min: Link<T> = Link::None;
         mem::swap(&mut min, &mut self.min);
 
-        self.min = FibonacciHeap::<T>::merge_entries(min, link);
+        self.min = self.merge_entries(min, link);
         self.size += 1;
+
+        handle
+    }
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap, `handle` was returned by another heap, or
+    /// `value` is greater than the current one.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, value: T) {
+        let mut link = handle.upgrade(&self.identity, "decrease_key");
+
+        assert!(!self.compare.less(&link.borrow().unwrap(), &value),
+                "Called decrease_key with a value greater than the current one");
+
+        link.set_value(value);
+
+        let parent = link.get_parent();
+        if !parent.is_none() && self.less(&link, &parent) {
+            self.cut(link.clone(), parent.clone());
+            self.cascading_cut(parent);
+        }
+
+        if self.less(&link, &self.min) {
+            self.min = link;
+        }
+    }
+
+    /// Remove the value referenced by `handle` from the heap and return it.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap or `handle` was returned by another heap.
+    pub fn delete(&mut self, handle: &Handle<T>) -> T {
+        let link = handle.upgrade(&self.identity, "delete");
+
+        let parent = link.get_parent();
+        if !parent.is_none() {
+            self.cut(link.clone(), parent.clone());
+            self.cascading_cut(parent);
+        }
+
+        // Pretend the entry is the smallest one, so pop removes it and consolidates the roots.
+        self.min = link;
+        self.pop().unwrap()
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -93,7 +170,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         let mut new_min = Link::None;
         mem::swap(&mut new_min, &mut self.min);
 
-        self.min = FibonacciHeap::<T>::merge_entries(new_min, min_child);
+        self.min = self.merge_entries(new_min, min_child);
 
         if self.size == 0 {
             return min.into_value();
@@ -124,7 +201,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
                 self.tree_table.push(Link::None);
                 let other = self.tree_table.swap_remove(link_degree);
 
-                let (mut min_link, mut max) = if link < other {
+                let (mut min_link, mut max) = if self.less(&link, &other) {
                     (link, other)
                 } else {
                     (other, link)
@@ -140,17 +217,17 @@ impl<T: PartialOrd> FibonacciHeap<T> {
                 max.set_next(&max_clone);
 
                 max.set_parent(&min_link);
+                max.set_marked(false);
 
                 let min_link_child = min_link.get_child();
-                min_link.set_child(&FibonacciHeap::<T>::merge_entries(min_link_child, max));
-                // max.is_marked = false;
+                min_link.set_child(&self.merge_entries(min_link_child, max));
 
                 min_link.inc_degree();
 
                 link = min_link;
             }
 
-            if link <= self.min {
+            if !self.less(&self.min, &link) {
                 self.min = link;
             }
         }
@@ -161,16 +238,173 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         min.into_value()
     }
 
-    pub fn merge(x: FibonacciHeap<T>, y: FibonacciHeap<T>) -> FibonacciHeap<T> {
-        let mut result = FibonacciHeap::new();
+    /// Merge two heaps into one, which keeps the ordering of `x`.
+    ///
+    /// See `append` for the cost and the handles.
+    pub fn merge(mut x: FibonacciHeap<T, C>, mut y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, which keeps its ordering, and leave `other`
+    /// empty.
+    ///
+    /// The root lists are spliced in O(1) time. Entries are shared with their handles, so
+    /// handles returned by `other` now refer to values of this heap and must be used with it.
+    pub fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        let mut min = Link::None;
+        mem::swap(&mut min, &mut self.min);
+
+        let mut other_min = Link::None;
+        mem::swap(&mut other_min, &mut other.min);
+
+        *other.identity.parent.borrow_mut() = Some(self.identity.clone());
+        other.identity = Identity::new();
+
+        self.min = self.merge_entries(min, other_min);
+        self.size += other.size;
+        other.size = 0;
+    }
+
+    /// Remove all values from the heap and return them from the smallest to the largest.
+    ///
+    /// Values are popped one by one, so the heap is left empty even if the iterator is
+    /// dropped early.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C> {
+        DrainSorted { heap: self }
+    }
+
+    /// Consume the heap and return its values from the smallest to the largest.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.size);
+
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+
+        values
+    }
+
+    /// Panic if the structure of the heap is broken: a value is smaller than its parent or
+    /// the minimum, an entry points at a wrong parent, a sibling list isn't a consistent ring,
+    /// a degree doesn't match the number of children, or the number of entries isn't `size`.
+    pub fn check_invariants(&self) {
+        let mut count = 0;
+        let mut rings = vec![(self.min.clone(), Link::None)];
+
+        while let Some((start, parent)) = rings.pop() {
+            if start.is_none() {
+                continue;
+            }
+
+            let mut current = start.clone();
+            let mut ring_len = 0;
+
+            loop {
+                count += 1;
+                ring_len += 1;
+                assert!(count <= self.size,
+                        "Invariant violated: more entries than size = {}",
+                        self.size);
+
+                assert!(current.get_parent().are_same(&parent),
+                        "Invariant violated: entry points at a wrong parent");
+                assert!(current.get_next().get_prev().are_same(&current) &&
+                        current.get_prev().get_next().are_same(&current),
+                        "Invariant violated: broken sibling ring");
+
+                if parent.is_none() {
+                    assert!(!self.less(&current, &self.min),
+                            "Invariant violated: root is smaller than the minimum");
+                } else {
+                    assert!(!self.less(&current, &parent),
+                            "Invariant violated: entry is smaller than its parent");
+                }
+
+                let child = current.get_child();
+                if child.is_none() {
+                    assert!(current.get_degree() == 0,
+                            "Invariant violated: degree of an entry without children isn't 0");
+                } else {
+                    rings.push((child, current.clone()));
+                }
+
+                current = current.get_next();
+                if current.are_same(&start) {
+                    break;
+                }
+            }
+
+            if !parent.is_none() {
+                assert!(parent.get_degree() == ring_len,
+                        "Invariant violated: degree = {} of an entry with {} children",
+                        parent.get_degree(),
+                        ring_len);
+            }
+        }
+
+        assert!(count == self.size,
+                "Invariant violated: {} entries, but size = {}",
+                count,
+                self.size);
+    }
+
+    fn cut(&mut self, mut link: Link<T>, mut parent: Link<T>) {
+        let mut next = link.get_next();
+
+        if next.are_same(&link) {
+            parent.set_child(&Link::None);
+        } else {
+            let mut prev = link.get_prev();
+            prev.set_next(&next);
+            next.set_prev(&prev);
+
+            if parent.get_child().are_same(&link) {
+                parent.set_child(&next);
+            }
+
+            let link_clone = link.clone();
+            link.set_prev(&link_clone);
+            link.set_next(&link_clone);
+        }
 
-        result.min = FibonacciHeap::<T>::merge_entries(x.min, y.min);
-        result.size = x.size + y.size;
+        parent.dec_degree();
+        link.set_parent(&Link::None);
+        link.set_marked(false);
+
+        let mut min = Link::None;
+        mem::swap(&mut min, &mut self.min);
 
-        result
+        self.min = self.merge_entries(min, link);
     }
 
-    fn merge_entries(mut x: Link<T>, mut y: Link<T>) -> Link<T> {
+    fn cascading_cut(&mut self, mut link: Link<T>) {
+        loop {
+            let parent = link.get_parent();
+            if parent.is_none() {
+                break;
+            }
+
+            if !link.is_marked() {
+                link.set_marked(true);
+                break;
+            }
+
+            self.cut(link, parent.clone());
+            link = parent;
+        }
+    }
+
+    #[inline]
+    fn less(&self, x: &Link<T>, y: &Link<T>) -> bool {
+        match (x.borrow(), y.borrow()) {
+            (Some(x), Some(y)) => self.compare.less(&x, &y),
+            _ => false,
+        }
+    }
+
+    fn merge_entries(&self, mut x: Link<T>, mut y: Link<T>) -> Link<T> {
         if x.is_none() && y.is_none() {
             Link::None
         } else if !x.is_none() && y.is_none() {
@@ -185,7 +419,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
             y.set_next(&x_next);
             x_next.set_prev(&y);
 
-            if x < y {
+            if self.less(&x, &y) {
                 x
             } else {
                 y
@@ -194,22 +428,408 @@ impl<T: PartialOrd> FibonacciHeap<T> {
     }
 }
 
-impl<T: PartialOrd> Entry<T> {
+impl<T, C> FibonacciHeap<T, C> {
+    /// Return an iterator over the values of the heap in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            entries: Entries::new(&self.min),
+            len: self.size,
+            marker: PhantomData,
+        }
+    }
+
+    /// Render the forest as a Graphviz digraph with an edge from every entry to each of its
+    /// children. The minimum has a double border and marked entries are dashed.
+    pub fn to_dot(&self) -> String
+        where T: fmt::Debug
+    {
+        let mut dot = String::from("digraph {\n");
+        let mut id = 0;
+        let mut rings = vec![(self.min.clone(), None)];
+
+        while let Some((start, parent)) = rings.pop() {
+            if start.is_none() {
+                continue;
+            }
+
+            let mut current = start.clone();
+
+            loop {
+                let label = format!("{:?}", *current.borrow().unwrap())
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"");
+                let style = if current.are_same(&self.min) {
+                    ", peripheries=2"
+                } else if current.is_marked() {
+                    ", style=dashed"
+                } else {
+                    ""
+                };
+
+                dot.push_str(&format!("    n{} [label=\"{}\"{}];\n", id, label, style));
+                if let Some(parent) = parent {
+                    dot.push_str(&format!("    n{} -> n{};\n", parent, id));
+                }
+
+                rings.push((current.get_child(), Some(id)));
+                id += 1;
+
+                current = current.get_next();
+                if current.are_same(&start) {
+                    break;
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Remove all values from the heap.
+    pub fn clear(&mut self) {
+        // Entries point at each other in circular lists, so every link has to be cleared to
+        // break the reference cycles.
+        let min = mem::replace(&mut self.min, Link::None);
+        self.size = 0;
+
+        for link in Entries::new(&min) {
+            link.clear_links();
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for FibonacciHeap<T, C> {
+    fn default() -> FibonacciHeap<T, C> {
+        FibonacciHeap::with_comparator(C::default())
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for FibonacciHeap<T, C> {
+    /// Copy the heap with the same tree structure. Handles to the values of the original heap
+    /// don't refer to the values of the copy.
+    fn clone(&self) -> FibonacciHeap<T, C> {
+        let mut copies = HashMap::new();
+
+        for link in Entries::new(&self.min) {
+            if let Link::Some(rc) = link {
+                let copy = {
+                    let entry = rc.borrow();
+                    let mut copy = Entry::new(entry.value.clone());
+                    copy.degree = entry.degree;
+                    copy.is_marked = entry.is_marked;
+
+                    Rc::new(RefCell::new(copy))
+                };
+
+                copies.insert(rc.as_ptr() as *const Entry<T>, (rc, copy));
+            }
+        }
+
+        let copy_link = |link: &Link<T>| match *link {
+            Link::None => Link::None,
+            Link::Some(ref rc) => Link::Some(copies[&(rc.as_ptr() as *const Entry<T>)].1.clone()),
+        };
+
+        for &(ref rc, ref copy) in copies.values() {
+            let entry = rc.borrow();
+            let mut copy = copy.borrow_mut();
+
+            copy.parent = copy_link(&entry.parent);
+            copy.next = copy_link(&entry.next);
+            copy.prev = copy_link(&entry.prev);
+            copy.child = copy_link(&entry.child);
+        }
+
+        FibonacciHeap {
+            identity: Identity::new(),
+            size: self.size,
+            min: copy_link(&self.min),
+            tree_table: Vec::new(),
+            to_visit: Vec::new(),
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for FibonacciHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for FibonacciHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> FibonacciHeap<T, C> {
+        let mut heap = FibonacciHeap::default();
+        heap.extend(iter);
+
+        heap
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, C> IntoIterator for FibonacciHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consume the heap and return an iterator over its values in arbitrary order.
+    fn into_iter(mut self) -> IntoIter<T> {
+        let min = mem::replace(&mut self.min, Link::None);
+        self.size = 0;
+
+        let links: Vec<_> = Entries::new(&min).collect();
+        for link in &links {
+            link.clear_links();
+        }
+
+        mem::drop(min);
+
+        let values: Vec<_> = links.into_iter().filter_map(|link| link.into_value()).collect();
+
+        IntoIter { values: values.into_iter() }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a FibonacciHeap<T, C> {
+    type Item = ValueRef<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over borrowed values of a `FibonacciHeap` in arbitrary order.
+pub struct Iter<'a, T: 'a> {
+    entries: Entries<T>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = ValueRef<'a, T>;
+
+    fn next(&mut self) -> Option<ValueRef<'a, T>> {
+        match self.entries.next() {
+            Some(Link::Some(rc)) => {
+                self.len -= 1;
+
+                Some(ValueRef {
+                    entry: rc,
+                    marker: PhantomData,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Value of a `FibonacciHeap` returned by `Iter`.
+///
+/// Values live in `RefCell`s which the iterator can't lend out for longer than one step, so
+/// every item keeps its entry and borrows the value on request.
+pub struct ValueRef<'a, T: 'a> {
+    entry: Rc<RefCell<Entry<T>>>,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> ValueRef<'a, T> {
+    pub fn value(&self) -> Ref<'_, T> {
+        Ref::map(self.entry.borrow(), |entry| &entry.value)
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for ValueRef<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value().fmt(f)
+    }
+}
+
+/// Iterator over the values of a consumed `FibonacciHeap` in arbitrary order.
+pub struct IntoIter<T> {
+    values: vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.values.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Iterator popping the values of a `FibonacciHeap` from the smallest to the largest.
+pub struct DrainSorted<'a, T: 'a, C: 'a> {
+    heap: &'a mut FibonacciHeap<T, C>,
+}
+
+impl<'a, T, C: Compare<T>> Iterator for DrainSorted<'a, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.size, Some(self.heap.size))
+    }
+}
+
+impl<'a, T, C: Compare<T>> ExactSizeIterator for DrainSorted<'a, T, C> {}
+
+impl<'a, T, C> Drop for DrainSorted<'a, T, C> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// Walks every entry of a forest, ring by ring. The links of an entry are read before it's
+/// returned, so the caller may clear them right away.
+struct Entries<T> {
+    rings: Vec<Link<T>>,
+    start: Link<T>,
+    current: Link<T>,
+}
+
+impl<T> Entries<T> {
+    fn new(min: &Link<T>) -> Entries<T> {
+        Entries {
+            rings: Vec::new(),
+            start: min.clone(),
+            current: min.clone(),
+        }
+    }
+}
+
+impl<T> Iterator for Entries<T> {
+    type Item = Link<T>;
+
+    fn next(&mut self) -> Option<Link<T>> {
+        if self.current.is_none() {
+            match self.rings.pop() {
+                None => return None,
+                Some(start) => {
+                    self.start = start.clone();
+                    self.current = start;
+                }
+            }
+        }
+
+        let current = self.current.clone();
+
+        let child = current.get_child();
+        if !child.is_none() {
+            self.rings.push(child);
+        }
+
+        let next = current.get_next();
+        self.current = if next.are_same(&self.start) {
+            Link::None
+        } else {
+            next
+        };
+
+        Some(current)
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for FibonacciHeap<T, C> {
+    type Ref<'a> = Ref<'a, T> where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        FibonacciHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<Ref<'a, T>> {
+        FibonacciHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        FibonacciHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        FibonacciHeap::is_empty(self)
+    }
+}
+
+impl<T, C: Compare<T>> MeldablePriorityQueue<T> for FibonacciHeap<T, C> {
+    fn merge(x: FibonacciHeap<T, C>, y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        FibonacciHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        FibonacciHeap::append(self, other)
+    }
+}
+
+impl<T, C: Compare<T>> AddressablePriorityQueue<T> for FibonacciHeap<T, C> {
+    type Handle = Handle<T>;
+
+    fn push_with_handle(&mut self, value: T) -> Handle<T> {
+        FibonacciHeap::push(self, value)
+    }
+
+    fn decrease_key(&mut self, handle: &Handle<T>, value: T) {
+        FibonacciHeap::decrease_key(self, handle, value)
+    }
+
+    fn delete(&mut self, handle: &Handle<T>) -> T {
+        FibonacciHeap::delete(self, handle)
+    }
+}
+
+impl<T, C> Drop for FibonacciHeap<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Entry<T> {
     pub fn new(value: T) -> Entry<T> {
         Entry {
             value: value,
 
             degree: 0,
-            // is_marked: false,
+            is_marked: false,
             parent: Link::None,
             next: Link::None,
             prev: Link::None,
             child: Link::None,
         }
     }
+
+    pub fn clear_links(&mut self) {
+        self.parent = Link::None;
+        self.next = Link::None;
+        self.prev = Link::None;
+        self.child = Link::None;
+    }
 }
 
-impl<T: PartialOrd> Link<T> {
+impl<T> Link<T> {
     pub fn is_none(&self) -> bool {
         match *self {
             Link::None => true,
@@ -217,6 +837,12 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
+    pub fn clear_links(&self) {
+        if let Link::Some(ref rc) = *self {
+            rc.borrow_mut().clear_links();
+        }
+    }
+
     pub fn new(value: T) -> Link<T> {
         let entry = Entry::new(value);
         let rc = Rc::new(RefCell::new(entry));
@@ -245,6 +871,43 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
+    pub fn dec_degree(&mut self) {
+        if let &mut Link::Some(ref rc) = self {
+            rc.borrow_mut().degree -= 1;
+        }
+    }
+
+    pub fn is_marked(&self) -> bool {
+        match *self {
+            Link::None => false,
+            Link::Some(ref rc) => rc.borrow().is_marked,
+        }
+    }
+
+    pub fn set_marked(&mut self, is_marked: bool) {
+        if let &mut Link::Some(ref rc) = self {
+            rc.borrow_mut().is_marked = is_marked;
+        }
+    }
+
+    pub fn set_value(&mut self, value: T) {
+        if let &mut Link::Some(ref rc) = self {
+            rc.borrow_mut().value = value;
+        }
+    }
+
+    pub fn handle(&self, heap: &Rc<Identity>) -> Handle<T> {
+        let entry = match *self {
+            Link::Some(ref rc) => Rc::downgrade(rc),
+            Link::None => Weak::new(),
+        };
+
+        Handle {
+            entry: entry,
+            heap: heap.clone(),
+        }
+    }
+
     pub fn into_value(mut self) -> Option<T> {
         self.set_next(&Link::None);
         self.set_prev(&Link::None);
@@ -260,7 +923,7 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
-    pub fn borrow(&self) -> Option<Ref<T>> {
+    pub fn borrow(&self) -> Option<Ref<'_, T>> {
         match *self {
             Link::Some(ref rc) => Some(Ref::map(rc.borrow(), |entry| &entry.value)),
             Link::None => None,
@@ -295,6 +958,13 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
+    pub fn get_parent(&self) -> Link<T> {
+        match *self {
+            Link::Some(ref rc) => rc.borrow().parent.clone(),
+            Link::None => Link::None,
+        }
+    }
+
     pub fn get_next(&self) -> Link<T> {
         match *self {
             Link::Some(ref rc) => {
@@ -350,43 +1020,60 @@ impl<T: PartialOrd> Link<T> {
     }
 }
 
-impl<T: PartialOrd> Clone for Link<T> {
-    fn clone(&self) -> Self {
-        match *self {
-            Link::None => Link::None,
-            Link::Some(ref rc) => Link::Some(rc.clone()),
+impl Identity {
+    fn new() -> Rc<Identity> {
+        Rc::new(Identity { parent: RefCell::new(None) })
+    }
+
+    /// Return the identity of the heap holding the values of the heap with `identity`, and
+    /// point every identity on the way straight at it.
+    fn find(identity: &Rc<Identity>) -> Rc<Identity> {
+        let mut root = identity.clone();
+        loop {
+            let parent = root.parent.borrow().clone();
+            match parent {
+                Some(parent) => root = parent,
+                None => break,
+            }
+        }
+
+        let mut current = identity.clone();
+        while !Rc::ptr_eq(&current, &root) {
+            let parent = current.parent.borrow_mut().replace(root.clone());
+            current = parent.unwrap();
         }
+
+        root
     }
 }
 
-impl<T: PartialOrd> PartialEq for Link<T> {
-    #[inline]
-    fn eq(&self, other: &Link<T>) -> bool {
-        match *self {
-            Link::None => false,
-            Link::Some(ref rc) => {
-                match *other {
-                    Link::None => false,
-                    Link::Some(ref other_rc) => rc.borrow().value.eq(&other_rc.borrow().value),
-                }
-            }
+impl<T> Handle<T> {
+    fn upgrade(&self, heap: &Rc<Identity>, method: &str) -> Link<T> {
+        assert!(Rc::ptr_eq(&Identity::find(&self.heap), heap),
+                "Called {} with a handle to a value of another heap",
+                method);
+
+        match self.entry.upgrade() {
+            Some(rc) => Link::Some(rc),
+            None => panic!("Called {} with a handle to a value that is no longer in the heap", method),
         }
     }
 }
 
-impl<T: PartialOrd> PartialOrd for Link<T> {
-    #[inline]
-    fn partial_cmp(&self, other: &Link<T>) -> Option<Ordering> {
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle {
+            entry: self.entry.clone(),
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<T> Clone for Link<T> {
+    fn clone(&self) -> Self {
         match *self {
-            Link::None => None,
-            Link::Some(ref rc) => {
-                match *other {
-                    Link::None => None,
-                    Link::Some(ref other_rc) => {
-                        rc.borrow().value.partial_cmp(&other_rc.borrow().value)
-                    }
-                }
-            }
+            Link::None => Link::None,
+            Link::Some(ref rc) => Link::Some(rc.clone()),
         }
     }
 }
@@ -441,6 +1128,312 @@ fn test_order() {
     assert_eq!(8, heap.pop().unwrap());
 }
 
+#[test]
+fn test_decrease_key() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i * 10)).collect();
+
+    // Consolidate, so the decreased entries have parents and cascading cuts happen.
+    assert_eq!(0, heap.pop().unwrap());
+
+    heap.decrease_key(&handles[9], 5);
+    heap.decrease_key(&handles[7], 15);
+    heap.decrease_key(&handles[8], 1);
+    heap.decrease_key(&handles[6], 16);
+
+    assert_eq!(1, *heap.min().unwrap());
+    assert_eq!(vec![1, 5, 10, 15, 16, 20, 30, 40, 50],
+               (0..9).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_decrease_key_popped() {
+    let mut heap = FibonacciHeap::new();
+
+    let handle = heap.push(1);
+    heap.pop();
+
+    heap.decrease_key(&handle, 0);
+}
+
+#[test]
+fn test_delete() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+    assert_eq!(5, heap.delete(&handles[5]));
+    assert_eq!(1, heap.delete(&handles[1]));
+    assert_eq!(9, heap.delete(&handles[9]));
+
+    assert_eq!(6, heap.size());
+    assert_eq!(vec![2, 3, 4, 6, 7, 8],
+               (0..6).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_append() {
+    struct Queue {
+        heap: FibonacciHeap<i32>,
+    }
+
+    let mut queue = Queue { heap: (0..5).map(|i| i * 2).collect() };
+    let mut other = FibonacciHeap::new();
+
+    let handle = other.push(9);
+    other.extend((0..4).map(|i| i * 2 + 1));
+    other.pop();
+
+    queue.heap.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(9, queue.heap.size());
+
+    queue.heap.decrease_key(&handle, 0);
+    other.push(1);
+
+    assert_eq!(vec![0, 0, 2, 3, 4, 5, 6, 7, 8], queue.heap.into_sorted_vec());
+    assert_eq!(vec![1], other.into_sorted_vec());
+}
+
+#[test]
+#[should_panic]
+fn test_handle_of_other_heap() {
+    let mut x = FibonacciHeap::new();
+    let mut y = FibonacciHeap::new();
+
+    let handle = x.push(2);
+    y.push(1);
+
+    y.decrease_key(&handle, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_handle_of_appended_heap() {
+    let mut x = FibonacciHeap::new();
+    let mut y = FibonacciHeap::new();
+    let mut z = FibonacciHeap::new();
+
+    let handle = y.push(2);
+    x.append(&mut y);
+    z.append(&mut x);
+
+    // The value of `handle` is in `z` now, not in `y`.
+    y.push(1);
+    y.delete(&handle);
+}
+
+#[test]
+fn test_max_order() {
+    use super::MaxOrder;
+
+    let mut heap = FibonacciHeap::with_comparator(MaxOrder);
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(8, *heap.min().unwrap());
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_comparator() {
+    use super::KeyOrder;
+
+    let mut by_key = FibonacciHeap::with_comparator(KeyOrder::new(|x: &(&str, i32)| x.1));
+    let mut by_closure = FibonacciHeap::with_comparator(|x: &(&str, i32), y: &(&str, i32)| x.0.cmp(y.0));
+
+    for &value in &[("b", 3), ("c", 1), ("a", 2)] {
+        by_key.push(value);
+        by_closure.push(value);
+    }
+
+    assert_eq!(vec!["c", "a", "b"], (0..3).map(|_| by_key.pop().unwrap().0).collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b", "c"],
+               (0..3).map(|_| by_closure.pop().unwrap().0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut heap = FibonacciHeap::new();
+
+        for i in 0..100 {
+            heap.push(DropCounter::new(i, &drops));
+        }
+
+        // Consolidate, so the remaining entries form trees with children.
+        heap.pop();
+        assert_eq!(1, drops.get());
+    }
+
+    assert_eq!(100, drops.get());
+}
+
+#[test]
+fn test_drop_merged() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut x = FibonacciHeap::new();
+        let mut y = FibonacciHeap::new();
+
+        for i in 0..10 {
+            x.push(DropCounter::new(i, &drops));
+            y.push(DropCounter::new(i, &drops));
+        }
+
+        x.pop();
+        y.pop();
+
+        let mut merged = FibonacciHeap::merge(x, y);
+        merged.pop();
+        assert_eq!(3, drops.get());
+    }
+
+    assert_eq!(20, drops.get());
+}
+
+#[test]
+fn test_iter() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+    heap.extend(vec![20, 10]);
+
+    let mut values: Vec<_> = heap.iter().map(|value| *value.value()).collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+
+    let mut values: Vec<_> = heap.into_iter().collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+}
+
+#[test]
+fn test_iter_appended() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    let mut other: FibonacciHeap<_> = (10..20).collect();
+    let handle = other.push(30);
+    heap.pop();
+    other.pop();
+    heap.append(&mut other);
+    heap.decrease_key(&handle, 0);
+
+    assert_eq!(0, other.iter().len());
+    assert_eq!(19, heap.iter().len());
+
+    let copy = heap.clone();
+    copy.check_invariants();
+
+    let mut values: Vec<_> = copy.iter().map(|value| *value.value()).collect();
+    values.sort();
+    assert_eq!(heap.into_sorted_vec(), values);
+}
+
+#[test]
+fn test_into_sorted_vec() {
+    let heap: FibonacciHeap<_> = vec![7, 1, 8, 4, 5, 2, 3, 6].into_iter().collect();
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], heap.into_sorted_vec());
+}
+
+#[test]
+fn test_drain_sorted() {
+    let mut heap: FibonacciHeap<_> = (0..10).rev().collect();
+
+    assert_eq!(vec![0, 1, 2], heap.drain_sorted().take(3).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+
+    heap.push(1);
+    assert_eq!(vec![1], heap.drain_sorted().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_clone() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    let handle = heap.push(15);
+    heap.pop();
+
+    let mut copy = heap.clone();
+    heap.decrease_key(&handle, 0);
+
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9], heap.into_sorted_vec());
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 15],
+               (0..10).map(|_| copy.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_debug() {
+    let heap: FibonacciHeap<_> = Some(1).into_iter().collect();
+
+    assert_eq!("[1]", format!("{:?}", heap));
+}
+
+#[test]
+fn test_check_invariants() {
+    let mut heap = FibonacciHeap::new();
+    heap.check_invariants();
+
+    let handles: Vec<_> = (0..50).map(|i| heap.push((i * 37) % 50 + 100)).collect();
+    heap.check_invariants();
+
+    heap.pop();
+    heap.check_invariants();
+
+    for (i, handle) in handles.iter().enumerate().skip(1).filter(|&(i, _)| i % 3 == 0) {
+        heap.decrease_key(handle, i as i32);
+        heap.check_invariants();
+    }
+
+    for handle in handles.iter().skip(1).take(20) {
+        heap.delete(handle);
+        heap.check_invariants();
+    }
+
+    while heap.pop().is_some() {
+        heap.check_invariants();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_check_invariants_broken() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+
+    heap.size += 1;
+    heap.check_invariants();
+}
+
+#[test]
+fn test_to_dot() {
+    let mut heap = FibonacciHeap::new();
+
+    for &i in &[3, 1, 2] {
+        heap.push(i);
+    }
+
+    heap.pop();
+
+    assert_eq!("digraph {\n    n0 [label=\"2\", peripheries=2];\n    n1 [label=\"3\"];\n    \
+                n0 -> n1;\n}\n",
+               heap.to_dot());
+}
+
 #[bench]
 fn bench_push_pop(b: &mut ::test::Bencher) {
     b.iter(|| {
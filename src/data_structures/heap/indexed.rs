@@ -0,0 +1,354 @@
+// Binary heap of integer keys ordered by their priorities. `positions` maps every key to its
+// index in `heap` and `priorities` stores the priority of every key, so keys can be found and
+// updated in O(log n) time. Both grow to fit the largest key pushed so far.
+//
+// `sequences` stores the number of pushes before every key, which orders keys with equal
+// priorities, so the queue is stable without wrapping priorities in `Stamped`.
+
+use std::usize;
+
+const NONE: usize = usize::MAX;
+
+/// Queue of integer keys ordered by their priorities.
+///
+/// Keys with equal priorities are popped in the order they were pushed. Changing the priority
+/// of a key keeps the position of its push in that order.
+pub struct IndexedMinPQ<P: PartialOrd> {
+    heap: Vec<usize>,
+    positions: Vec<usize>,
+    priorities: Vec<Option<P>>,
+    sequences: Vec<u64>,
+    sequence: u64,
+}
+
+impl<P: PartialOrd> IndexedMinPQ<P> {
+    pub fn new() -> IndexedMinPQ<P> {
+        IndexedMinPQ {
+            heap: Vec::new(),
+            positions: Vec::new(),
+            priorities: Vec::new(),
+            sequences: Vec::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Create a queue with room for keys from `0` to `capacity - 1`.
+    pub fn with_capacity(capacity: usize) -> IndexedMinPQ<P> {
+        let mut queue = IndexedMinPQ {
+            heap: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            priorities: Vec::with_capacity(capacity),
+            sequences: Vec::with_capacity(capacity),
+            sequence: 0,
+        };
+
+        queue.reserve_key(capacity);
+
+        queue
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        key < self.positions.len() && self.positions[key] != NONE
+    }
+
+    /// Return the priority of `key`, if it's in the queue.
+    pub fn priority(&self, key: usize) -> Option<&P> {
+        match self.priorities.get(key) {
+            Some(&Some(ref priority)) => Some(priority),
+            _ => None,
+        }
+    }
+
+    /// Return the key with the smallest priority and its priority.
+    pub fn min(&self) -> Option<(usize, &P)> {
+        self.heap.first().map(|&key| (key, self.priority_of(key)))
+    }
+
+    /// Add `key` with `priority` to the queue.
+    ///
+    /// ##Panics
+    /// If `key` is already in the queue.
+    pub fn push(&mut self, key: usize, priority: P) {
+        assert!(!self.contains(key),
+                "Called push with key = {} which is already in the queue",
+                key);
+
+        self.reserve_key(key + 1);
+
+        let index = self.heap.len();
+        self.heap.push(key);
+        self.positions[key] = index;
+        self.priorities[key] = Some(priority);
+        self.sequences[key] = self.sequence;
+        self.sequence += 1;
+
+        self.sift_up(index);
+    }
+
+    /// Remove the key with the smallest priority and return it with its priority.
+    pub fn pop_min(&mut self) -> Option<(usize, P)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let key = self.heap[0];
+        self.remove(key).map(|priority| (key, priority))
+    }
+
+    /// Set the priority of `key` to `priority`, which may be smaller or greater than the
+    /// current one.
+    ///
+    /// ##Panics
+    /// If `key` is not in the queue.
+    pub fn change_key(&mut self, key: usize, priority: P) {
+        assert!(self.contains(key),
+                "Called change_key with key = {} which is not in the queue",
+                key);
+
+        self.priorities[key] = Some(priority);
+
+        let index = self.sift_up(self.positions[key]);
+        self.sift_down(index);
+    }
+
+    /// Set the priority of `key` to a smaller `priority`.
+    ///
+    /// ##Panics
+    /// If `key` is not in the queue or `priority` is greater than the current one.
+    pub fn decrease_key(&mut self, key: usize, priority: P) {
+        assert!(self.contains(key),
+                "Called decrease_key with key = {} which is not in the queue",
+                key);
+        assert!(!(priority > *self.priority_of(key)),
+                "Called decrease_key with a priority greater than the current one");
+
+        self.priorities[key] = Some(priority);
+
+        let index = self.positions[key];
+        self.sift_up(index);
+    }
+
+    /// Remove `key` from the queue and return its priority, if it was in the queue.
+    pub fn remove(&mut self, key: usize) -> Option<P> {
+        if !self.contains(key) {
+            return None;
+        }
+
+        let index = self.positions[key];
+        let last = self.heap.len() - 1;
+        self.swap(index, last);
+
+        self.heap.pop();
+        self.positions[key] = NONE;
+
+        if index < last {
+            let index = self.sift_up(index);
+            self.sift_down(index);
+        }
+
+        self.priorities[key].take()
+    }
+
+    fn reserve_key(&mut self, len: usize) {
+        while self.positions.len() < len {
+            self.positions.push(NONE);
+            self.priorities.push(None);
+            self.sequences.push(0);
+        }
+    }
+
+    #[inline]
+    fn priority_of(&self, key: usize) -> &P {
+        match self.priorities[key] {
+            Some(ref priority) => priority,
+            None => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn less(&self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.heap[x], self.heap[y]);
+        let (x_priority, y_priority) = (self.priority_of(x), self.priority_of(y));
+
+        x_priority < y_priority ||
+        !(y_priority < x_priority) && self.sequences[x] < self.sequences[y]
+    }
+
+    #[inline]
+    fn swap(&mut self, x: usize, y: usize) {
+        self.heap.swap(x, y);
+        self.positions[self.heap[x]] = x;
+        self.positions[self.heap[y]] = y;
+    }
+
+    /// Move the key at `index` up until its parent is not greater and return its new index.
+    fn sift_up(&mut self, mut index: usize) -> usize {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+
+            if self.less(index, parent) {
+                self.swap(index, parent);
+                index = parent;
+            } else {
+                break;
+            }
+        }
+
+        index
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.heap.len();
+
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+
+            if left >= len {
+                break;
+            }
+
+            let child = if right < len && self.less(right, left) {
+                right
+            } else {
+                left
+            };
+
+            if self.less(child, index) {
+                self.swap(index, child);
+                index = child;
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+impl<P: PartialOrd> Default for IndexedMinPQ<P> {
+    fn default() -> IndexedMinPQ<P> {
+        IndexedMinPQ::new()
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut queue = IndexedMinPQ::new();
+
+    queue.push(3, 1);
+
+    assert_eq!(1, queue.size());
+}
+
+#[test]
+fn test_contains() {
+    let mut queue = IndexedMinPQ::with_capacity(2);
+
+    queue.push(5, 1);
+
+    assert!(queue.contains(5));
+    assert!(!queue.contains(1));
+    assert!(!queue.contains(10));
+
+    queue.pop_min();
+
+    assert!(!queue.contains(5));
+}
+
+#[test]
+fn test_order() {
+    let mut queue = IndexedMinPQ::new();
+
+    for (key, &priority) in [7, 1, 8, 4, 5, 2, 3, 6].iter().enumerate() {
+        queue.push(key, priority);
+    }
+
+    assert_eq!(Some((1, &1)), queue.min());
+    assert_eq!(vec![(1, 1), (5, 2), (6, 3), (3, 4), (4, 5), (7, 6), (0, 7), (2, 8)],
+               (0..8).map(|_| queue.pop_min().unwrap()).collect::<Vec<_>>());
+    assert!(queue.pop_min().is_none());
+}
+
+#[test]
+fn test_change_key() {
+    let mut queue = IndexedMinPQ::new();
+
+    for key in 0..10 {
+        queue.push(key, key * 10);
+    }
+
+    queue.change_key(0, 55);
+    queue.change_key(9, 5);
+    queue.decrease_key(4, 1);
+
+    assert_eq!(Some(&55), queue.priority(0));
+    assert_eq!(vec![4, 9, 1, 2, 3, 5, 0, 6, 7, 8],
+               (0..10).map(|_| queue.pop_min().unwrap().0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_stable() {
+    let mut queue = IndexedMinPQ::new();
+
+    for key in 0..10 {
+        queue.push(9 - key, key % 3);
+    }
+
+    queue.pop_min();
+    queue.push(20, 0);
+    queue.change_key(8, 2);
+    queue.decrease_key(1, 1);
+
+    // Keys 8 and 1 keep the places of their pushes among equal priorities.
+    assert_eq!(vec![6, 3, 0, 20, 5, 2, 1, 8, 7, 4],
+               (0..10).map(|_| queue.pop_min().unwrap().0).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn test_decrease_key_greater() {
+    let mut queue = IndexedMinPQ::new();
+
+    queue.push(0, 1);
+
+    queue.decrease_key(0, 2);
+}
+
+#[test]
+fn test_remove() {
+    let mut queue = IndexedMinPQ::new();
+
+    for key in 0..10 {
+        queue.push(key, key);
+    }
+
+    assert_eq!(Some(5), queue.remove(5));
+    assert_eq!(Some(0), queue.remove(0));
+    assert_eq!(None, queue.remove(0));
+    assert_eq!(None, queue.remove(20));
+
+    assert_eq!(vec![1, 2, 3, 4, 6, 7, 8, 9],
+               (0..8).map(|_| queue.pop_min().unwrap().0).collect::<Vec<_>>());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut queue = IndexedMinPQ::with_capacity(10000);
+
+        for i in 0..10000 {
+            queue.push(i, 10000 - i);
+        }
+
+        for _ in 0..10000 {
+            queue.pop_min();
+        }
+    })
+}
@@ -0,0 +1,283 @@
+// Based on "A Data Structure for Manipulating Priority Queues" by Jean Vuillemin.
+//
+// Unlike the eager version, push and merge only add trees to an unordered root list.
+// Trees of the same rank are linked together by pop, which makes push O(1) and pop O(log n)
+// amortized.
+
+use std::mem;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct BinomialHeap<T: PartialOrd> {
+    size: usize,
+    min: Option<usize>,
+    roots: Vec<Box<Node<T>>>,
+    tree_table: Vec<Option<Box<Node<T>>>>,
+}
+
+struct Node<T: PartialOrd> {
+    pub value: T,
+    // Child at index `i` is a binomial tree of rank `i`.
+    pub children: Vec<Box<Node<T>>>,
+}
+
+impl<T: PartialOrd> BinomialHeap<T> {
+    pub fn new() -> BinomialHeap<T> {
+        BinomialHeap {
+            size: 0,
+            min: None,
+            roots: Vec::new(),
+            tree_table: Vec::new(),
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.min.map(|index| &self.roots[index].value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.roots.push(Box::new(Node::new(value)));
+        self.size += 1;
+
+        let index = self.roots.len() - 1;
+        self.update_min(index);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let min = match self.min.take() {
+            None => return None,
+            Some(min) => min,
+        };
+
+        let node = *self.roots.swap_remove(min);
+        self.size -= 1;
+
+        self.roots.extend(node.children);
+
+        for mut tree in self.roots.drain(..) {
+            loop {
+                let rank = tree.children.len();
+
+                while rank >= self.tree_table.len() {
+                    self.tree_table.push(None);
+                }
+
+                match self.tree_table[rank].take() {
+                    None => {
+                        self.tree_table[rank] = Some(tree);
+                        break;
+                    }
+                    Some(other) => tree = Node::link(tree, other),
+                }
+            }
+        }
+
+        for tree in self.tree_table.drain(..) {
+            if let Some(tree) = tree {
+                self.roots.push(tree);
+            }
+        }
+
+        for index in 0..self.roots.len() {
+            self.update_min(index);
+        }
+
+        Some(node.value)
+    }
+
+    /// Merge two heaps into one.
+    ///
+    /// Roots of the heap with fewer trees are moved to the other one, so this takes time
+    /// proportional to the shorter root list.
+    pub fn merge(mut x: BinomialHeap<T>, mut y: BinomialHeap<T>) -> BinomialHeap<T> {
+        if x.roots.len() < y.roots.len() {
+            mem::swap(&mut x, &mut y);
+        }
+
+        let offset = x.roots.len();
+        x.roots.append(&mut y.roots);
+        x.size += y.size;
+
+        if let Some(min) = y.min {
+            x.update_min(min + offset);
+        }
+
+        x
+    }
+
+    fn update_min(&mut self, index: usize) {
+        match self.min {
+            Some(min) if !(self.roots[index].value < self.roots[min].value) => {}
+            _ => self.min = Some(index),
+        }
+    }
+}
+
+impl<T: PartialOrd> Default for BinomialHeap<T> {
+    fn default() -> BinomialHeap<T> {
+        BinomialHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for BinomialHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        BinomialHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinomialHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        BinomialHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        BinomialHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinomialHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> MeldablePriorityQueue<T> for BinomialHeap<T> {
+    fn merge(x: BinomialHeap<T>, y: BinomialHeap<T>) -> BinomialHeap<T> {
+        BinomialHeap::merge(x, y)
+    }
+}
+
+impl<T: PartialOrd> Node<T> {
+    pub fn new(value: T) -> Node<T> {
+        Node {
+            value: value,
+            children: Vec::new(),
+        }
+    }
+
+    /// Link two trees of the same rank into one tree of the next rank.
+    pub fn link(mut x: Box<Node<T>>, mut y: Box<Node<T>>) -> Box<Node<T>> {
+        if y.value < x.value {
+            y.children.push(x);
+            y
+        } else {
+            x.children.push(y);
+            x
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size);
+}
+
+#[test]
+fn test_min() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_pop() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.pop().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = BinomialHeap::new();
+
+    heap.push(7);
+    heap.push(1);
+    heap.push(8);
+    heap.push(4);
+    heap.push(5);
+    heap.push(2);
+    heap.push(3);
+    heap.push(6);
+
+    assert_eq!(1, heap.pop().unwrap());
+    assert_eq!(2, heap.pop().unwrap());
+    assert_eq!(3, heap.pop().unwrap());
+    assert_eq!(4, heap.pop().unwrap());
+    assert_eq!(5, heap.pop().unwrap());
+    assert_eq!(6, heap.pop().unwrap());
+    assert_eq!(7, heap.pop().unwrap());
+    assert_eq!(8, heap.pop().unwrap());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = BinomialHeap::new();
+    let mut y = BinomialHeap::new();
+
+    for i in 0..7 {
+        x.push(i * 2);
+    }
+
+    for i in 0..5 {
+        y.push(i * 2 + 1);
+    }
+
+    y.pop();
+
+    let mut merged = BinomialHeap::merge(x, y);
+
+    assert_eq!(11, merged.size());
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12],
+               (0..11).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_lazy_push() {
+    let mut heap = BinomialHeap::new();
+
+    for i in 0..11 {
+        heap.push(i);
+    }
+
+    assert_eq!(11, heap.roots.len());
+
+    heap.pop();
+
+    // 10 = 0b1010
+    let mut ranks: Vec<_> = heap.roots.iter().map(|tree| tree.children.len()).collect();
+    ranks.sort();
+    assert_eq!(vec![1, 3], ranks);
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = BinomialHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
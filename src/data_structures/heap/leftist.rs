@@ -0,0 +1,265 @@
+// Based on "Linear Lists and Priority Queues as Balanced Binary Trees" by Clark A. Crane.
+//
+// Every node stores its rank, the length of the shortest path to a missing child. The rank of
+// the left child is never smaller than the rank of the right one, so right paths have O(log n)
+// nodes and merging along them takes O(log n) time.
+
+use std::mem;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct LeftistHeap<T: PartialOrd> {
+    size: usize,
+    root: Tree<T>,
+}
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+struct Node<T: PartialOrd> {
+    pub value: T,
+    pub rank: usize,
+    pub left: Tree<T>,
+    pub right: Tree<T>,
+}
+
+impl<T: PartialOrd> LeftistHeap<T> {
+    pub fn new() -> LeftistHeap<T> {
+        LeftistHeap {
+            size: 0,
+            root: None,
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) {
+        let root = self.root.take();
+        self.root = merge_trees(root, Some(Box::new(Node::new(value))));
+        self.size += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.root.take().map(|mut root| {
+            self.root = merge_trees(root.left.take(), root.right.take());
+            self.size -= 1;
+
+            root.value
+        })
+    }
+
+    pub fn merge(mut x: LeftistHeap<T>, mut y: LeftistHeap<T>) -> LeftistHeap<T> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, leaving `other` empty.
+    pub fn append(&mut self, other: &mut LeftistHeap<T>) {
+        let root = self.root.take();
+        self.root = merge_trees(root, other.root.take());
+        self.size += mem::replace(&mut other.size, 0);
+    }
+}
+
+/// Merge the right paths of `x` and `y`. Merging is done in a loop instead of recursively,
+/// and so are the other traversals, since left paths may have O(n) nodes.
+fn merge_trees<T: PartialOrd>(mut x: Tree<T>, mut y: Tree<T>) -> Tree<T> {
+    let mut path = Vec::new();
+
+    let mut tree = loop {
+        match (x, y) {
+            (None, tree) | (tree, None) => break tree,
+            (Some(x_root), Some(y_root)) => {
+                let (mut min, max) = if y_root.value < x_root.value {
+                    (y_root, x_root)
+                } else {
+                    (x_root, y_root)
+                };
+
+                x = min.right.take();
+                y = Some(max);
+                path.push(min);
+            }
+        }
+    };
+
+    while let Some(mut node) = path.pop() {
+        node.right = tree;
+
+        if rank(&node.left) < rank(&node.right) {
+            mem::swap(&mut node.left, &mut node.right);
+        }
+
+        node.rank = rank(&node.right) + 1;
+        tree = Some(node);
+    }
+
+    tree
+}
+
+#[inline]
+fn rank<T: PartialOrd>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |node| node.rank)
+}
+
+impl<T: PartialOrd> Default for LeftistHeap<T> {
+    fn default() -> LeftistHeap<T> {
+        LeftistHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for LeftistHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        LeftistHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LeftistHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        LeftistHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        LeftistHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        LeftistHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> MeldablePriorityQueue<T> for LeftistHeap<T> {
+    fn merge(x: LeftistHeap<T>, y: LeftistHeap<T>) -> LeftistHeap<T> {
+        LeftistHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut LeftistHeap<T>) {
+        LeftistHeap::append(self, other)
+    }
+}
+
+impl<T: PartialOrd> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        let mut nodes: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = nodes.pop() {
+            nodes.extend(node.left.take());
+            nodes.extend(node.right.take());
+        }
+    }
+}
+
+impl<T: PartialOrd> Node<T> {
+    pub fn new(value: T) -> Node<T> {
+        Node {
+            value: value,
+            rank: 1,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = LeftistHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let mut heap = LeftistHeap::new();
+
+    heap.push(2);
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = LeftistHeap::new();
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.pop().is_none());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = LeftistHeap::new();
+    let mut y = LeftistHeap::new();
+
+    for i in 0..7 {
+        x.push(i * 2);
+    }
+
+    for i in 0..5 {
+        y.push(i * 2 + 1);
+    }
+
+    y.pop();
+
+    let mut merged = LeftistHeap::merge(x, y);
+
+    assert_eq!(11, merged.size());
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12],
+               (0..11).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_rank() {
+    let mut heap = LeftistHeap::new();
+
+    for i in 0..1000 {
+        heap.push(i);
+    }
+
+    assert!(heap.root.as_ref().unwrap().rank <= 10);
+}
+
+#[test]
+fn test_drop_long_path() {
+    let mut heap = LeftistHeap::new();
+
+    // Every value becomes the new root with the old root as its left child.
+    for i in 0..100000 {
+        heap.push(-i);
+    }
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = LeftistHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
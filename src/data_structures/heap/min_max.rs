@@ -0,0 +1,352 @@
+// Based on "Min-Max Heaps and Generalized Priority Queues" by M. D. Atkinson, J.-R. Sack,
+// N. Santoro and T. Strothotte.
+//
+// Implicit binary heap where entries on even levels are smaller than all of their
+// descendants and entries on odd levels are greater than all of their descendants. The
+// smallest value is the root and the largest one is one of its children.
+
+use std::mem;
+
+use super::PriorityQueue;
+
+pub struct MinMaxHeap<T: PartialOrd> {
+    entries: Vec<T>,
+}
+
+impl<T: PartialOrd> MinMaxHeap<T> {
+    pub fn new() -> MinMaxHeap<T> {
+        MinMaxHeap { entries: Vec::new() }
+    }
+
+    /// Build a heap from `values` in O(n) time.
+    pub fn from_vec(values: Vec<T>) -> MinMaxHeap<T> {
+        let mut heap = MinMaxHeap { entries: values };
+        let len = heap.entries.len();
+
+        for index in (0..len / 2).rev() {
+            heap.trickle_down(index);
+        }
+
+        heap
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.entries.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.entries[index])
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.entries.push(value);
+
+        let index = self.entries.len() - 1;
+        self.bubble_up(index);
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.remove(0))
+        }
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.max_index().map(|index| self.remove(index))
+    }
+
+    /// Push `value` and pop the largest value, which may be `value` itself.
+    ///
+    /// This is faster than `push` followed by `pop_max`, and keeps the size of the heap, so
+    /// it's the way to offer a value to a buffer of the `k` smallest values.
+    pub fn push_pop_max(&mut self, value: T) -> T {
+        let index = match self.max_index() {
+            Some(index) if value < self.entries[index] => index,
+            _ => return value,
+        };
+
+        let max = mem::replace(&mut self.entries[index], value);
+
+        if index > 0 {
+            if self.entries[index] < self.entries[0] {
+                self.entries.swap(index, 0);
+            }
+
+            self.trickle_down(index);
+        }
+
+        max
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.entries.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => {
+                if self.entries[1] < self.entries[2] {
+                    Some(2)
+                } else {
+                    Some(1)
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        let value = self.entries.swap_remove(index);
+
+        if index < self.entries.len() {
+            self.trickle_down(index);
+        }
+
+        value
+    }
+
+    /// Return `true` if the entry at `x` belongs before the entry at `y` on a level of the
+    /// given kind, that is if it's smaller on min levels and greater on max levels.
+    #[inline]
+    fn before(&self, x: usize, y: usize, is_max: bool) -> bool {
+        if is_max {
+            self.entries[y] < self.entries[x]
+        } else {
+            self.entries[x] < self.entries[y]
+        }
+    }
+
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+
+        let parent = (index - 1) / 2;
+        let is_max = is_max_level(index);
+
+        // The entry is on the wrong side of its parent, so it belongs to the levels of the
+        // other kind.
+        if self.before(parent, index, is_max) {
+            self.entries.swap(index, parent);
+            self.bubble_up_grandparents(parent, !is_max);
+        } else {
+            self.bubble_up_grandparents(index, is_max);
+        }
+    }
+
+    fn bubble_up_grandparents(&mut self, mut index: usize, is_max: bool) {
+        while index > 2 {
+            let grandparent = ((index - 1) / 2 - 1) / 2;
+
+            if self.before(index, grandparent, is_max) {
+                self.entries.swap(index, grandparent);
+                index = grandparent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        let is_max = is_max_level(index);
+        let len = self.entries.len();
+
+        loop {
+            let first_child = 2 * index + 1;
+            if first_child >= len {
+                break;
+            }
+
+            // Find the first among children and grandchildren in the order of the level.
+            let mut first = first_child;
+            let descendants = [first_child + 1,
+                               2 * first_child + 1,
+                               2 * first_child + 2,
+                               2 * first_child + 3,
+                               2 * first_child + 4];
+
+            for &descendant in &descendants {
+                if descendant < len && self.before(descendant, first, is_max) {
+                    first = descendant;
+                }
+            }
+
+            if !self.before(first, index, is_max) {
+                break;
+            }
+
+            self.entries.swap(first, index);
+
+            if first <= first_child + 1 {
+                break;
+            }
+
+            let parent = (first - 1) / 2;
+            if self.before(parent, first, is_max) {
+                self.entries.swap(first, parent);
+            }
+
+            index = first;
+        }
+    }
+}
+
+#[inline]
+fn is_max_level(index: usize) -> bool {
+    let level = 0usize.leading_zeros() - (index + 1).leading_zeros() - 1;
+
+    level % 2 == 1
+}
+
+impl<T: PartialOrd> Default for MinMaxHeap<T> {
+    fn default() -> MinMaxHeap<T> {
+        MinMaxHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for MinMaxHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        MinMaxHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MinMaxHeap::pop_min(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        MinMaxHeap::peek_min(self)
+    }
+
+    fn size(&self) -> usize {
+        MinMaxHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        MinMaxHeap::is_empty(self)
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = MinMaxHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_peek() {
+    let mut heap = MinMaxHeap::new();
+
+    assert_eq!(None, heap.peek_min());
+    assert_eq!(None, heap.peek_max());
+
+    heap.push(2);
+    assert_eq!(Some(&2), heap.peek_min());
+    assert_eq!(Some(&2), heap.peek_max());
+
+    heap.push(1);
+    heap.push(3);
+    assert_eq!(Some(&1), heap.peek_min());
+    assert_eq!(Some(&3), heap.peek_max());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = MinMaxHeap::new();
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8],
+               (0..8).map(|_| heap.pop_min().unwrap()).collect::<Vec<_>>());
+    assert!(heap.pop_min().is_none());
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1],
+               (0..8).map(|_| heap.pop_max().unwrap()).collect::<Vec<_>>());
+    assert!(heap.pop_max().is_none());
+}
+
+#[test]
+fn test_both_ends() {
+    let mut heap = MinMaxHeap::new();
+    let mut values: Vec<_> = (0..100).map(|i| (i * 37) % 100).collect();
+
+    for &value in &values {
+        heap.push(value);
+    }
+
+    values.sort();
+
+    for i in 0..50 {
+        if i % 3 == 0 {
+            assert_eq!(values.pop(), heap.pop_max());
+        } else {
+            assert_eq!(Some(values.remove(0)), heap.pop_min());
+        }
+
+        assert_eq!(values.first(), heap.peek_min());
+        assert_eq!(values.last(), heap.peek_max());
+    }
+}
+
+#[test]
+fn test_from_vec() {
+    let mut heap = MinMaxHeap::from_vec((0..20).map(|i| (i * 7) % 20).collect());
+
+    assert_eq!(Some(&19), heap.peek_max());
+    assert_eq!((0..20).collect::<Vec<_>>(),
+               (0..20).map(|_| heap.pop_min().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_push_pop_max() {
+    // Keep the 5 smallest values.
+    let mut heap = MinMaxHeap::new();
+
+    for i in 0..5 {
+        heap.push(i * 10);
+    }
+
+    assert_eq!(50, heap.push_pop_max(50));
+    assert_eq!(40, heap.push_pop_max(5));
+    assert_eq!(30, heap.push_pop_max(-1));
+    assert_eq!(20, heap.push_pop_max(15));
+
+    assert_eq!(5, heap.size());
+    assert_eq!(vec![-1, 0, 5, 10, 15],
+               (0..5).map(|_| heap.pop_min().unwrap()).collect::<Vec<_>>());
+
+    assert_eq!(1, heap.push_pop_max(1));
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = MinMaxHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop_min();
+        }
+    })
+}
@@ -1,8 +1,342 @@
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::Deref;
+
+pub use self::binary::BinaryHeap;
+pub use self::binomial::BinomialHeap;
+pub use self::lazy_binomial::BinomialHeap as LazyBinomialHeap;
+pub use self::dary::DaryHeap;
+pub use self::dary::Handle as DaryHandle;
+pub use self::indexed::IndexedMinPQ;
+pub use self::min_max::MinMaxHeap;
+pub use self::leftist::LeftistHeap;
+pub use self::persistent_leftist::LeftistHeap as PersistentLeftistHeap;
+pub use self::skew::SkewHeap;
+pub use self::persistent_skew::SkewHeap as PersistentSkewHeap;
+pub use self::radix::{RadixHeap, RadixKey};
+pub use self::multi_queue::MultiQueue;
 pub use self::fibonacci::FibonacciHeap;
+pub use self::fibonacci::Handle as FibonacciHandle;
 pub use self::unsafe_fibonacci::FibonacciHeap as UnsafeFibonacciHeap;
+pub use self::unsafe_fibonacci::Handle as UnsafeFibonacciHandle;
+pub use self::arena_fibonacci::FibonacciHeap as ArenaFibonacciHeap;
+pub use self::arena_fibonacci::Handle as ArenaFibonacciHandle;
+pub use self::pairing::PairingHeap;
+pub use self::pairing::Handle as PairingHandle;
+pub use self::stable::{Stable, StableRef, StableHandle, Stamped, StableOrder};
 
+mod binary;
+mod binomial;
+mod lazy_binomial;
+mod dary;
+mod indexed;
+mod min_max;
+mod leftist;
+mod persistent_leftist;
+mod skew;
+mod persistent_skew;
+mod radix;
+mod multi_queue;
 mod fibonacci;
 mod unsafe_fibonacci;
+mod arena_fibonacci;
+mod pairing;
+mod stable;
+
+/// Trait encompassing orderings of heap values. Heaps pop the value that is the smallest
+/// according to their ordering.
+///
+/// Closures comparing two values implement it, so any ordering can be used without newtypes.
+pub trait Compare<T> {
+    /// Compare `x` with `y`.
+    fn compare(&self, x: &T, y: &T) -> Ordering;
+
+    /// Return `true` if `x` goes before `y`.
+    fn less(&self, x: &T, y: &T) -> bool {
+        self.compare(x, y) == Ordering::Less
+    }
+}
+
+/// Ordering of `PartialOrd` values from the smallest to the largest. Default for every heap.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MinOrder;
+
+/// Ordering of `PartialOrd` values from the largest to the smallest, which makes a max-heap.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MaxOrder;
+
+/// Ordering of values by the key extracted from them, from the smallest to the largest.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyOrder<F> {
+    key: F,
+}
+
+impl<T: PartialOrd> Compare<T> for MinOrder {
+    fn compare(&self, x: &T, y: &T) -> Ordering {
+        x.partial_cmp(y).unwrap_or(Ordering::Equal)
+    }
+
+    #[inline]
+    fn less(&self, x: &T, y: &T) -> bool {
+        x < y
+    }
+}
+
+impl<T: PartialOrd> Compare<T> for MaxOrder {
+    fn compare(&self, x: &T, y: &T) -> Ordering {
+        y.partial_cmp(x).unwrap_or(Ordering::Equal)
+    }
+
+    #[inline]
+    fn less(&self, x: &T, y: &T) -> bool {
+        y < x
+    }
+}
+
+impl<F> KeyOrder<F> {
+    /// Create an ordering that compares the keys returned by `key`.
+    pub fn new(key: F) -> KeyOrder<F> {
+        KeyOrder { key: key }
+    }
+}
+
+impl<T, K: PartialOrd, F: Fn(&T) -> K> Compare<T> for KeyOrder<F> {
+    fn compare(&self, x: &T, y: &T) -> Ordering {
+        (self.key)(x).partial_cmp(&(self.key)(y)).unwrap_or(Ordering::Equal)
+    }
+
+    #[inline]
+    fn less(&self, x: &T, y: &T) -> bool {
+        (self.key)(x) < (self.key)(y)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, x: &T, y: &T) -> Ordering {
+        self(x, y)
+    }
+}
+
+/// Trait encompassing all min-heaps of this module.
+pub trait PriorityQueue<T> {
+    /// Borrowed smallest value returned by `min`.
+    type Ref<'a>: Deref<Target = T> where Self: 'a;
+
+    /// Add `value` to the queue.
+    fn push(&mut self, value: T);
+
+    /// Remove the smallest value from the queue and return it.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Return the smallest value without removing it.
+    fn min<'a>(&'a self) -> Option<Self::Ref<'a>>;
+
+    /// Return the number of values in the queue.
+    fn size(&self) -> usize;
+
+    /// Return `true` if the queue has no values.
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+}
+
+/// Trait encompassing priority queues that can be merged together.
+pub trait MeldablePriorityQueue<T>: PriorityQueue<T> + Sized {
+    /// Merge two queues into one containing the values of both.
+    ///
+    /// Handles returned by `x` stay valid. `FibonacciHeap` and `UnsafeFibonacciHeap` leave the
+    /// values of `y` where they are, so its handles refer to values of the merged queue, in
+    /// O(1) time. `ArenaFibonacciHeap` and `PairingHeap` keep their values in one arena, so
+    /// they move the values of `y` in time proportional to its slots, and its handles panic.
+    fn merge(x: Self, y: Self) -> Self;
+
+    /// Move all values of `other` into this queue, leaving `other` empty.
+    ///
+    /// Costs and handles are as for `merge`, with `other` as `y`. The default implementation
+    /// merges both queues into this one and replaces `other` with a new queue.
+    fn append(&mut self, other: &mut Self)
+        where Self: Default
+    {
+        let x = mem::replace(self, Self::default());
+        let y = mem::replace(other, Self::default());
+
+        *self = Self::merge(x, y);
+    }
+}
+
+/// Trait encompassing priority queues that can change values after they were pushed.
+pub trait AddressablePriorityQueue<T>: PriorityQueue<T> {
+    /// Stable reference to a pushed value.
+    type Handle;
+
+    /// Add `value` to the queue and return a handle to it.
+    fn push_with_handle(&mut self, value: T) -> Self::Handle;
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    fn decrease_key(&mut self, handle: &Self::Handle, value: T);
+
+    /// Remove the value referenced by `handle` from the queue and return it.
+    fn delete(&mut self, handle: &Self::Handle) -> T;
+}
+
+/// Test value that counts how many times it was dropped.
+#[cfg(test)]
+struct DropCounter<'a> {
+    value: i32,
+    drops: &'a ::std::cell::Cell<usize>,
+}
+
+#[cfg(test)]
+impl<'a> DropCounter<'a> {
+    fn new(value: i32, drops: &'a ::std::cell::Cell<usize>) -> DropCounter<'a> {
+        DropCounter {
+            value: value,
+            drops: drops,
+        }
+    }
+}
+
+#[cfg(test)]
+impl<'a> Drop for DropCounter<'a> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[cfg(test)]
+impl<'a> PartialEq for DropCounter<'a> {
+    fn eq(&self, other: &DropCounter<'a>) -> bool {
+        self.value == other.value
+    }
+}
+
+#[cfg(test)]
+impl<'a> PartialOrd for DropCounter<'a> {
+    fn partial_cmp(&self, other: &DropCounter<'a>) -> Option<::std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+#[cfg(test)]
+fn check_priority_queue<H: PriorityQueue<i32> + Default>() {
+    let mut heap = H::default();
+
+    assert!(heap.is_empty());
+    assert!(heap.min().is_none());
+    assert!(heap.pop().is_none());
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(8, heap.size());
+    assert_eq!(1, *heap.min().unwrap());
+
+    for i in 1..9 {
+        assert_eq!(i, heap.pop().unwrap());
+    }
+
+    assert!(heap.is_empty());
+}
+
+#[cfg(test)]
+fn check_meldable_priority_queue<H: MeldablePriorityQueue<i32> + Default>() {
+    let mut x = H::default();
+    let mut y = H::default();
+
+    for i in 0..5 {
+        x.push(i * 2);
+        y.push(i * 2 + 1);
+    }
+
+    let mut heap = H::merge(x, y);
+
+    assert_eq!(10, heap.size());
+    for i in 0..10 {
+        assert_eq!(i, heap.pop().unwrap());
+    }
+
+    let mut other = H::default();
+
+    for i in 0..5 {
+        heap.push(i * 2);
+        other.push(i * 2 + 1);
+    }
+
+    heap.append(&mut other);
+
+    assert!(other.is_empty());
+    assert_eq!(10, heap.size());
+    for i in 0..10 {
+        assert_eq!(i, heap.pop().unwrap());
+    }
+}
+
+#[cfg(test)]
+fn check_addressable_priority_queue<H: AddressablePriorityQueue<i32> + Default>() {
+    let mut heap = H::default();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push_with_handle(i * 10)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+
+    heap.decrease_key(&handles[9], 5);
+    assert_eq!(50, heap.delete(&handles[5]));
+
+    assert_eq!(vec![5, 10, 20, 30, 40, 60, 70, 80],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_compare() {
+    assert!(MinOrder.less(&1, &2));
+    assert!(!MinOrder.less(&2, &2));
+    assert!(MaxOrder.less(&2, &1));
+    assert_eq!(Ordering::Greater, MaxOrder.compare(&1, &2));
+    assert!(KeyOrder::new(|x: &(i32, i32)| x.1).less(&(2, 1), &(1, 2)));
+    assert!((|x: &i32, y: &i32| y.cmp(x)).less(&2, &1));
+}
+
+#[test]
+fn test_priority_queue() {
+    check_priority_queue::<BinaryHeap<i32>>();
+    check_priority_queue::<BinomialHeap<i32>>();
+    check_priority_queue::<LazyBinomialHeap<i32>>();
+    check_priority_queue::<DaryHeap<i32>>();
+    check_priority_queue::<MinMaxHeap<i32>>();
+    check_priority_queue::<FibonacciHeap<i32>>();
+    check_priority_queue::<UnsafeFibonacciHeap<i32>>();
+    check_priority_queue::<ArenaFibonacciHeap<i32>>();
+    check_priority_queue::<PairingHeap<i32>>();
+    check_priority_queue::<LeftistHeap<i32>>();
+    check_priority_queue::<PersistentLeftistHeap<i32>>();
+    check_priority_queue::<SkewHeap<i32>>();
+    check_priority_queue::<PersistentSkewHeap<i32>>();
+}
+
+#[test]
+fn test_meldable_priority_queue() {
+    check_meldable_priority_queue::<BinaryHeap<i32>>();
+    check_meldable_priority_queue::<BinomialHeap<i32>>();
+    check_meldable_priority_queue::<LazyBinomialHeap<i32>>();
+    check_meldable_priority_queue::<FibonacciHeap<i32>>();
+    check_meldable_priority_queue::<FibonacciHeap<i32, MinOrder>>();
+    check_meldable_priority_queue::<UnsafeFibonacciHeap<i32>>();
+    check_meldable_priority_queue::<ArenaFibonacciHeap<i32>>();
+    check_meldable_priority_queue::<PairingHeap<i32>>();
+    check_meldable_priority_queue::<LeftistHeap<i32>>();
+    check_meldable_priority_queue::<PersistentLeftistHeap<i32>>();
+    check_meldable_priority_queue::<SkewHeap<i32>>();
+    check_meldable_priority_queue::<PersistentSkewHeap<i32>>();
+}
+
+#[test]
+fn test_addressable_priority_queue() {
+    check_addressable_priority_queue::<DaryHeap<i32>>();
+    check_addressable_priority_queue::<FibonacciHeap<i32>>();
+    check_addressable_priority_queue::<ArenaFibonacciHeap<i32>>();
+    check_addressable_priority_queue::<PairingHeap<i32>>();
+}
 
 #[bench]
 fn bench_push_pop_binary(b: &mut ::test::Bencher) {
@@ -0,0 +1,338 @@
+// Based on "MultiQueues: Simple Relaxed Concurrent Priority Queues" by Hamza Rihani, Peter
+// Sanders and Roman Dementiev.
+//
+// Values are spread over several binary heaps, each behind its own lock. Push adds a value to
+// a random heap, and pop looks at two random heaps and takes the smaller of their minimums, so
+// threads rarely wait for each other.
+
+use std::cell::Cell;
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::BinaryHeap;
+
+/// Priority queue which can be shared by threads, for example through an `Arc`.
+///
+/// Every pushed value is popped exactly once, but the order is relaxed: `pop` returns one of
+/// the smallest values rather than the smallest one. With `q` internal heaps, the popped value
+/// is on average among the `O(q)` smallest ones. With a single heap, values are popped in
+/// order, like from a locked `BinaryHeap`.
+///
+/// `pop` returns `None` only after finding every heap empty. Values pushed by other threads
+/// while it looks through the heaps may be missed, so `None` means that the queue was empty
+/// at some point during the call, not that it's empty afterwards. Likewise, `size` is a
+/// snapshot which other threads may change right away.
+///
+/// Values which compare equal are popped in no particular order.
+///
+/// A thread panicking while it holds a heap doesn't make the queue unusable: other threads
+/// keep using the heap with the values it holds.
+pub struct MultiQueue<T: Ord> {
+    size: AtomicUsize,
+    heaps: Vec<Mutex<BinaryHeap<T>>>,
+}
+
+impl<T: Ord> MultiQueue<T> {
+    /// Create a queue spreading values over `heaps` binary heaps. Twice the number of threads
+    /// using the queue is a good choice.
+    ///
+    /// ##Panics
+    /// If `heaps` is 0.
+    pub fn new(heaps: usize) -> MultiQueue<T> {
+        assert!(heaps > 0, "Called new with heaps = 0");
+
+        MultiQueue {
+            size: AtomicUsize::new(0),
+            heaps: (0..heaps).map(|_| Mutex::new(BinaryHeap::new())).collect(),
+        }
+    }
+
+    /// Return the number of values in the queue at some point during the call.
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Add `value` to a random heap which isn't locked by another thread.
+    pub fn push(&self, value: T) {
+        // Count the value before it can be popped, so the size never drops below 0.
+        self.size.fetch_add(1, Ordering::SeqCst);
+
+        for _ in 0..self.heaps.len() {
+            if let Some(mut heap) = self.try_lock(random(self.heaps.len())) {
+                heap.push(value);
+                return;
+            }
+        }
+
+        // Random heaps keep being locked, so wait for one of them.
+        let heap = &self.heaps[random(self.heaps.len())];
+        heap.lock().unwrap_or_else(|error| error.into_inner()).push(value);
+    }
+
+    /// Remove one of the smallest values from the queue and return it.
+    pub fn pop(&self) -> Option<T> {
+        for _ in 0..self.heaps.len() {
+            let x = random(self.heaps.len());
+            let y = random(self.heaps.len());
+
+            let mut x_heap = match self.try_lock(x) {
+                Some(heap) => heap,
+                None => continue,
+            };
+
+            // Settle for the first heap if the second one is locked by another thread.
+            let mut y_heap = if x == y {
+                None
+            } else {
+                self.try_lock(y)
+            };
+
+            let is_y_smaller = match (y_heap.as_ref().and_then(|heap| heap.min()), x_heap.min()) {
+                (Some(y_min), Some(x_min)) => y_min < x_min,
+                (Some(_), None) => true,
+                _ => false,
+            };
+
+            let value = match y_heap {
+                Some(ref mut heap) if is_y_smaller => heap.pop(),
+                _ => x_heap.pop(),
+            };
+
+            if value.is_some() {
+                self.size.fetch_sub(1, Ordering::SeqCst);
+                return value;
+            }
+        }
+
+        // Random heaps keep being locked or empty, so look through all of them.
+        for heap in &self.heaps {
+            let value = heap.lock().unwrap_or_else(|error| error.into_inner()).pop();
+
+            if value.is_some() {
+                self.size.fetch_sub(1, Ordering::SeqCst);
+                return value;
+            }
+        }
+
+        None
+    }
+
+    /// Lock the heap with index `index`, or return `None` if another thread holds it. A heap
+    /// poisoned by a panicking thread is used as it is.
+    fn try_lock(&self, index: usize) -> Option<MutexGuard<'_, BinaryHeap<T>>> {
+        match self.heaps[index].try_lock() {
+            Ok(heap) => Some(heap),
+            Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+thread_local!(static RANDOM_STATE: Cell<u64> = Cell::new(random_seed()));
+
+/// Return a different seed for every thread.
+fn random_seed() -> u64 {
+    static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+    let thread = THREADS.fetch_add(1, Ordering::Relaxed) as u64;
+
+    // Spread consecutive thread numbers over the whole range with the SplitMix64 finalizer.
+    let mut seed = thread.wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15);
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    (seed ^ (seed >> 31)) | 1
+}
+
+/// Return a pseudorandom number from `0` to `bound - 1` using a xorshift generator local to
+/// the thread.
+fn random(bound: usize) -> usize {
+    RANDOM_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+
+        (x % bound as u64) as usize
+    })
+}
+
+#[test]
+fn test_size() {
+    let queue = MultiQueue::new(4);
+
+    queue.push(1);
+    queue.push(2);
+
+    assert_eq!(2, queue.size());
+
+    queue.pop();
+
+    assert_eq!(1, queue.size());
+}
+
+#[test]
+fn test_single_heap_order() {
+    let queue = MultiQueue::new(1);
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        queue.push(i);
+    }
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8],
+               (0..8).map(|_| queue.pop().unwrap()).collect::<Vec<_>>());
+    assert!(queue.pop().is_none());
+}
+
+#[test]
+fn test_relaxed_order() {
+    let queue = MultiQueue::new(4);
+
+    for i in 0..1000 {
+        queue.push(i);
+    }
+
+    let mut popped: Vec<_> = (0..1000).map(|_| queue.pop().unwrap()).collect();
+
+    // The first values come from the smallest ones of every heap.
+    assert!(popped[..10].iter().all(|&value| value < 200));
+    assert!(queue.pop().is_none());
+
+    popped.sort();
+    assert_eq!((0..1000).collect::<Vec<_>>(), popped);
+}
+
+#[test]
+fn test_send_sync() {
+    fn check<T: Send + Sync>() {}
+
+    check::<MultiQueue<i32>>();
+}
+
+#[test]
+fn test_poisoned() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let queue = Arc::new(MultiQueue::new(1));
+    queue.push(2);
+
+    let poisoner = queue.clone();
+    let result = thread::spawn(move || {
+            let _heap = poisoner.heaps[0].lock().unwrap();
+            panic!("poison the heap");
+        })
+        .join();
+
+    assert!(result.is_err());
+    assert!(queue.heaps[0].is_poisoned());
+
+    queue.push(1);
+
+    assert_eq!(Some(1), queue.pop());
+    assert_eq!(Some(2), queue.pop());
+    assert_eq!(None, queue.pop());
+}
+
+#[test]
+fn test_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let queue = Arc::new(MultiQueue::new(8));
+
+    let threads: Vec<_> = (0..4)
+        .map(|thread| {
+            let queue = queue.clone();
+
+            thread::spawn(move || {
+                let mut popped = Vec::new();
+
+                for i in 0..10000 {
+                    queue.push(thread * 10000 + i);
+
+                    if i % 2 == 0 {
+                        popped.extend(queue.pop());
+                    }
+                }
+
+                popped
+            })
+        })
+        .collect();
+
+    let mut popped: Vec<_> = threads.into_iter()
+        .flat_map(|thread| thread.join().unwrap())
+        .collect();
+
+    while let Some(value) = queue.pop() {
+        popped.push(value);
+    }
+
+    popped.sort();
+    assert_eq!((0..40000).collect::<Vec<_>>(), popped);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_threads_scheduler() {
+    use std::sync::Arc;
+    use std::thread;
+
+    // Every task with priority `p > 0` spawns two tasks with priority `p - 1`, until all threads
+    // agree that no tasks are left.
+    let queue = Arc::new(MultiQueue::new(8));
+    let pending = Arc::new(AtomicUsize::new(1));
+    queue.push(10);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let queue = queue.clone();
+            let pending = pending.clone();
+
+            thread::spawn(move || {
+                let mut done = 0;
+
+                while pending.load(Ordering::SeqCst) > 0 {
+                    if let Some(priority) = queue.pop() {
+                        if priority > 0 {
+                            pending.fetch_add(2, Ordering::SeqCst);
+                            queue.push(priority - 1);
+                            queue.push(priority - 1);
+                        }
+
+                        pending.fetch_sub(1, Ordering::SeqCst);
+                        done += 1;
+                    }
+                }
+
+                done
+            })
+        })
+        .collect();
+
+    let done: usize = threads.into_iter().map(|thread| thread.join().unwrap()).sum();
+
+    assert_eq!((1 << 11) - 1, done);
+    assert!(queue.is_empty());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let queue = MultiQueue::new(4);
+
+        for i in 1..10001 {
+            queue.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            queue.pop();
+        }
+    })
+}
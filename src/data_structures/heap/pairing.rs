@@ -0,0 +1,568 @@
+// Based on "The Pairing Heap: A New Form of Self-Adjusting Heap" by Fredman, Sedgewick,
+// Sleator and Tarjan.
+//
+// Entries live in a single Vec and refer to each other by index. Every entry keeps its leftmost
+// child and its right sibling; `prev` points to the left sibling or, for the leftmost child, to
+// the parent. Slots of removed entries are reused through a free list threaded through `next`.
+
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::usize;
+
+use super::{PriorityQueue, MeldablePriorityQueue, AddressablePriorityQueue};
+
+const NONE: usize = usize::MAX;
+
+/// Pairing heap with its entries in one arena.
+///
+/// Melding links two trees in O(1) time, but `merge` first moves the entries of `y` into the
+/// arena of `x`, which takes time proportional to the slots of `y`. Handles returned by `x`
+/// stay valid, while handles returned by `y` panic once it's merged.
+pub struct PairingHeap<T: PartialOrd> {
+    // Handles remember the heap they were returned by, since their slots mean nothing in
+    // another heap.
+    id: usize,
+    size: usize,
+    root: usize,
+    free: usize,
+    entries: Vec<Entry<T>>,
+    to_pair: Vec<usize>,
+}
+
+struct Entry<T: PartialOrd> {
+    pub value: Option<T>,
+    pub generation: usize,
+
+    pub child: usize,
+    pub next: usize,
+    pub prev: usize,
+}
+
+/// Stable reference to a value pushed into a `PairingHeap`.
+///
+/// Slots are reused, so the handle also remembers the generation of its slot, and the heap it
+/// was returned by. Using a handle whose value was already popped, deleted or moved by `merge`
+/// panics instead of touching the new occupant.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Handle {
+    heap: usize,
+    index: usize,
+    generation: usize,
+}
+
+impl<T: PartialOrd> PairingHeap<T> {
+    pub fn new() -> PairingHeap<T> {
+        static HEAPS: AtomicUsize = AtomicUsize::new(0);
+
+        PairingHeap {
+            id: HEAPS.fetch_add(1, Ordering::Relaxed),
+            size: 0,
+            root: NONE,
+            free: NONE,
+            entries: Vec::new(),
+            to_pair: Vec::new(),
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        if self.root == NONE {
+            None
+        } else {
+            Some(self.value(self.root))
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == NONE
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) -> Handle {
+        let index = self.allocate(value);
+        let handle = Handle {
+            heap: self.id,
+            index: index,
+            generation: self.entries[index].generation,
+        };
+
+        let root = self.root;
+        self.root = self.link(root, index);
+        self.size += 1;
+
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.size -= 1;
+
+        let root = self.root;
+        let child = self.entries[root].child;
+        self.root = self.combine_siblings(child);
+
+        self.release(root)
+    }
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap or `value` is greater than the current one.
+    pub fn decrease_key(&mut self, handle: &Handle, value: T) {
+        let index = self.index_of(handle, "decrease_key");
+
+        assert!(!(value > *self.value(index)),
+                "Called decrease_key with a value greater than the current one");
+
+        self.entries[index].value = Some(value);
+
+        if index != self.root {
+            self.detach(index);
+
+            let root = self.root;
+            self.root = self.link(root, index);
+        }
+    }
+
+    /// Remove the value referenced by `handle` from the heap and return it.
+    ///
+    /// ##Panics
+    /// If the value was already removed from the heap.
+    pub fn delete(&mut self, handle: &Handle) -> T {
+        let index = self.index_of(handle, "delete");
+
+        if index != self.root {
+            self.detach(index);
+
+            // Pretend the entry is the smallest one, so pop removes it and pairs its children.
+            let root = self.root;
+            self.attach(index, root);
+            self.root = index;
+        }
+
+        self.pop().unwrap()
+    }
+
+    /// Merge two heaps into one.
+    ///
+    /// Entries of `y` are moved after the entries of `x`, so this takes time proportional to
+    /// the slots of `y`. Handles returned by `x` stay valid, while handles returned by `y` panic.
+    pub fn merge(mut x: PairingHeap<T>, y: PairingHeap<T>) -> PairingHeap<T> {
+        let offset = x.entries.len();
+        let shift = |index: usize| if index == NONE { NONE } else { index + offset };
+
+        let y_root = shift(y.root);
+        let mut y_free = shift(y.free);
+
+        for mut entry in y.entries {
+            entry.child = shift(entry.child);
+            entry.next = shift(entry.next);
+            entry.prev = shift(entry.prev);
+
+            x.entries.push(entry);
+        }
+
+        while y_free != NONE {
+            let next = x.entries[y_free].next;
+            x.entries[y_free].next = x.free;
+            x.free = y_free;
+            y_free = next;
+        }
+
+        let x_root = x.root;
+        x.root = x.link(x_root, y_root);
+        x.size += y.size;
+
+        x
+    }
+
+    fn allocate(&mut self, value: T) -> usize {
+        if self.free == NONE {
+            self.entries.push(Entry::new(value));
+
+            return self.entries.len() - 1;
+        }
+
+        let index = self.free;
+        let entry = &mut self.entries[index];
+        self.free = entry.next;
+
+        entry.value = Some(value);
+        entry.child = NONE;
+        entry.next = NONE;
+        entry.prev = NONE;
+
+        index
+    }
+
+    fn release(&mut self, index: usize) -> Option<T> {
+        let entry = &mut self.entries[index];
+        entry.generation = entry.generation.wrapping_add(1);
+        entry.next = self.free;
+        self.free = index;
+
+        entry.value.take()
+    }
+
+    fn index_of(&self, handle: &Handle, method: &str) -> usize {
+        match self.entries.get(handle.index) {
+            Some(entry) if handle.heap == self.id && entry.generation == handle.generation &&
+                           entry.value.is_some() => handle.index,
+            _ => {
+                panic!("Called {} with a handle to a value that is no longer in the heap",
+                       method)
+            }
+        }
+    }
+
+    #[inline]
+    fn value(&self, index: usize) -> &T {
+        match self.entries[index].value {
+            Some(ref value) => value,
+            None => unreachable!(),
+        }
+    }
+
+    /// Cut the subtree rooted at `index` out of its parent's list of children.
+    fn detach(&mut self, index: usize) {
+        let prev = self.entries[index].prev;
+        let next = self.entries[index].next;
+
+        if self.entries[prev].child == index {
+            self.entries[prev].child = next;
+        } else {
+            self.entries[prev].next = next;
+        }
+
+        if next != NONE {
+            self.entries[next].prev = prev;
+        }
+
+        self.entries[index].prev = NONE;
+        self.entries[index].next = NONE;
+    }
+
+    /// Make the tree rooted at `child` the leftmost child of `parent`.
+    fn attach(&mut self, parent: usize, child: usize) {
+        let parent_child = self.entries[parent].child;
+
+        self.entries[child].next = parent_child;
+        self.entries[child].prev = parent;
+        if parent_child != NONE {
+            self.entries[parent_child].prev = child;
+        }
+
+        self.entries[parent].child = child;
+    }
+
+    fn link(&mut self, x: usize, y: usize) -> usize {
+        if x == NONE {
+            y
+        } else if y == NONE {
+            x
+        } else if self.value(y) < self.value(x) {
+            self.attach(y, x);
+            y
+        } else {
+            self.attach(x, y);
+            x
+        }
+    }
+
+    /// Pair up the siblings starting at `first` from left to right, then meld the pairs from
+    /// right to left, and return the root of the resulting tree.
+    fn combine_siblings(&mut self, first: usize) -> usize {
+        let mut to_pair = mem::replace(&mut self.to_pair, Vec::new());
+
+        let mut current = first;
+        while current != NONE {
+            let next = self.entries[current].next;
+
+            self.entries[current].prev = NONE;
+            self.entries[current].next = NONE;
+            to_pair.push(current);
+
+            current = next;
+        }
+
+        let mut i = 0;
+        while i + 1 < to_pair.len() {
+            to_pair[i / 2] = self.link(to_pair[i], to_pair[i + 1]);
+            i += 2;
+        }
+
+        if i < to_pair.len() {
+            to_pair[i / 2] = to_pair[i];
+            i += 2;
+        }
+
+        let mut root = NONE;
+        for &tree in to_pair[..i / 2].iter().rev() {
+            root = self.link(tree, root);
+        }
+
+        to_pair.clear();
+        self.to_pair = to_pair;
+
+        root
+    }
+}
+
+impl<T: PartialOrd> Default for PairingHeap<T> {
+    fn default() -> PairingHeap<T> {
+        PairingHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for PairingHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        PairingHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        PairingHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        PairingHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        PairingHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> MeldablePriorityQueue<T> for PairingHeap<T> {
+    fn merge(x: PairingHeap<T>, y: PairingHeap<T>) -> PairingHeap<T> {
+        PairingHeap::merge(x, y)
+    }
+}
+
+impl<T: PartialOrd> AddressablePriorityQueue<T> for PairingHeap<T> {
+    type Handle = Handle;
+
+    fn push_with_handle(&mut self, value: T) -> Handle {
+        PairingHeap::push(self, value)
+    }
+
+    fn decrease_key(&mut self, handle: &Handle, value: T) {
+        PairingHeap::decrease_key(self, handle, value)
+    }
+
+    fn delete(&mut self, handle: &Handle) -> T {
+        PairingHeap::delete(self, handle)
+    }
+}
+
+impl<T: PartialOrd> Entry<T> {
+    pub fn new(value: T) -> Entry<T> {
+        Entry {
+            value: Some(value),
+            generation: 0,
+
+            child: NONE,
+            next: NONE,
+            prev: NONE,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = PairingHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size);
+}
+
+#[test]
+fn test_min() {
+    let mut heap = PairingHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_pop() {
+    let mut heap = PairingHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.pop().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = PairingHeap::new();
+
+    heap.push(7);
+    heap.push(1);
+    heap.push(8);
+    heap.push(4);
+    heap.push(5);
+    heap.push(2);
+    heap.push(3);
+    heap.push(6);
+
+    assert_eq!(1, heap.pop().unwrap());
+    assert_eq!(2, heap.pop().unwrap());
+    assert_eq!(3, heap.pop().unwrap());
+    assert_eq!(4, heap.pop().unwrap());
+    assert_eq!(5, heap.pop().unwrap());
+    assert_eq!(6, heap.pop().unwrap());
+    assert_eq!(7, heap.pop().unwrap());
+    assert_eq!(8, heap.pop().unwrap());
+}
+
+#[test]
+fn test_decrease_key() {
+    let mut heap = PairingHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i * 10)).collect();
+
+    // Pair the entries up, so the decreased entries are nested in the tree.
+    assert_eq!(0, heap.pop().unwrap());
+
+    heap.decrease_key(&handles[9], 5);
+    heap.decrease_key(&handles[7], 15);
+    heap.decrease_key(&handles[8], 1);
+    heap.decrease_key(&handles[6], 16);
+
+    assert_eq!(1, *heap.min().unwrap());
+    assert_eq!(vec![1, 5, 10, 15, 16, 20, 30, 40, 50],
+               (0..9).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_decrease_key_popped() {
+    let mut heap = PairingHeap::new();
+
+    let handle = heap.push(1);
+    heap.pop();
+    heap.push(2);
+
+    heap.decrease_key(&handle, 0);
+}
+
+#[test]
+fn test_delete() {
+    let mut heap = PairingHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+    assert_eq!(5, heap.delete(&handles[5]));
+    assert_eq!(1, heap.delete(&handles[1]));
+    assert_eq!(9, heap.delete(&handles[9]));
+
+    assert_eq!(6, heap.size());
+    assert_eq!(vec![2, 3, 4, 6, 7, 8],
+               (0..6).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = PairingHeap::new();
+    let mut y = PairingHeap::new();
+
+    let handle = x.push(10);
+    for i in 0..5 {
+        x.push(i * 2);
+        y.push(i * 2 + 1);
+    }
+
+    x.pop();
+    y.pop();
+
+    let mut merged = PairingHeap::merge(x, y);
+    merged.decrease_key(&handle, 0);
+
+    assert_eq!(9, merged.size());
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9],
+               (0..9).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_merge_smaller() {
+    let mut x = PairingHeap::new();
+    let mut y = PairingHeap::new();
+
+    let handle = x.push(10);
+    y.push(5);
+    for i in 0..5 {
+        y.push(i * 2 + 1);
+    }
+
+    // Handles of `x` stay valid even if `y` has more entries.
+    let mut merged = PairingHeap::merge(x, y);
+    merged.decrease_key(&handle, 0);
+
+    assert_eq!(vec![0, 1, 3, 5, 5, 7, 9],
+               (0..7).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn test_merge_moved_handle() {
+    let mut x = PairingHeap::new();
+    let mut y = PairingHeap::new();
+
+    x.push(1);
+    x.push(2);
+    let handle = y.push(3);
+
+    let mut merged = PairingHeap::merge(x, y);
+    merged.delete(&handle);
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut heap = PairingHeap::new();
+
+        for i in 0..100 {
+            heap.push(DropCounter::new(i, &drops));
+        }
+
+        heap.pop();
+        assert_eq!(1, drops.get());
+    }
+
+    assert_eq!(100, drops.get());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = PairingHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
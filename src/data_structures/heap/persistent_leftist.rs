@@ -0,0 +1,306 @@
+// Based on "Linear Lists and Priority Queues as Balanced Binary Trees" by Clark A. Crane.
+//
+// Unlike the mutable version, nodes are shared through `Rc` and never change. Merging copies
+// the nodes on the right paths it goes along and shares the rest, so every operation returns
+// a new heap in O(log n) time and leaves the old one valid.
+
+use std::rc::Rc;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct LeftistHeap<T: PartialOrd + Clone> {
+    size: usize,
+    root: Tree<T>,
+}
+
+type Tree<T> = Option<Rc<Node<T>>>;
+
+struct Node<T: PartialOrd + Clone> {
+    pub value: T,
+    pub rank: usize,
+    pub left: Tree<T>,
+    pub right: Tree<T>,
+}
+
+impl<T: PartialOrd + Clone> LeftistHeap<T> {
+    pub fn new() -> LeftistHeap<T> {
+        LeftistHeap {
+            size: 0,
+            root: None,
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Return a new heap with the values of this one and `value`.
+    pub fn push(&self, value: T) -> LeftistHeap<T> {
+        let node = Node::new(value, None, None);
+
+        LeftistHeap {
+            size: self.size + 1,
+            root: merge_trees(self.root.clone(), Some(Rc::new(node))),
+        }
+    }
+
+    /// Return the smallest value and a new heap with the rest of the values of this one.
+    pub fn pop(&self) -> Option<(&T, LeftistHeap<T>)> {
+        self.root.as_ref().map(|root| {
+            let rest = LeftistHeap {
+                size: self.size - 1,
+                root: merge_trees(root.left.clone(), root.right.clone()),
+            };
+
+            (&root.value, rest)
+        })
+    }
+
+    /// Return a new heap with the values of this one and `other`.
+    pub fn merge(&self, other: &LeftistHeap<T>) -> LeftistHeap<T> {
+        LeftistHeap {
+            size: self.size + other.size,
+            root: merge_trees(self.root.clone(), other.root.clone()),
+        }
+    }
+}
+
+/// Merge the right paths of `x` and `y` into copies of their nodes.
+fn merge_trees<T: PartialOrd + Clone>(mut x: Tree<T>, mut y: Tree<T>) -> Tree<T> {
+    let mut path = Vec::new();
+
+    let mut tree = loop {
+        match (x, y) {
+            (None, tree) | (tree, None) => break tree,
+            (Some(x_root), Some(y_root)) => {
+                let (min, max) = if y_root.value < x_root.value {
+                    (y_root, x_root)
+                } else {
+                    (x_root, y_root)
+                };
+
+                x = min.right.clone();
+                y = Some(max);
+                path.push(min);
+            }
+        }
+    };
+
+    while let Some(node) = path.pop() {
+        let copy = Node::new(node.value.clone(), node.left.clone(), tree);
+        tree = Some(Rc::new(copy));
+    }
+
+    tree
+}
+
+#[inline]
+fn rank<T: PartialOrd + Clone>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |node| node.rank)
+}
+
+impl<T: PartialOrd + Clone> Clone for LeftistHeap<T> {
+    /// Return a heap sharing all nodes with this one in O(1) time.
+    fn clone(&self) -> LeftistHeap<T> {
+        LeftistHeap {
+            size: self.size,
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Default for LeftistHeap<T> {
+    fn default() -> LeftistHeap<T> {
+        LeftistHeap::new()
+    }
+}
+
+impl<T: PartialOrd + Clone> PriorityQueue<T> for LeftistHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        *self = LeftistHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (value, rest) = match LeftistHeap::pop(self) {
+            None => return None,
+            Some((value, rest)) => (value.clone(), rest),
+        };
+
+        *self = rest;
+        Some(value)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        LeftistHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        LeftistHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        LeftistHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd + Clone> MeldablePriorityQueue<T> for LeftistHeap<T> {
+    fn merge(x: LeftistHeap<T>, y: LeftistHeap<T>) -> LeftistHeap<T> {
+        LeftistHeap::merge(&x, &y)
+    }
+}
+
+impl<T: PartialOrd + Clone> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        // Only nodes which aren't shared with other heaps are freed, and their children are
+        // collected instead of dropped recursively.
+        let mut nodes: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(rc) = nodes.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(rc) {
+                nodes.extend(node.left.take());
+                nodes.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Node<T> {
+    /// Create a node with the children swapped if needed to keep the left one higher ranked.
+    pub fn new(value: T, left: Tree<T>, right: Tree<T>) -> Node<T> {
+        let (left, right) = if rank(&left) < rank(&right) {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        Node {
+            value: value,
+            rank: rank(&right) + 1,
+            left: left,
+            right: right,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let heap = LeftistHeap::new().push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let heap = LeftistHeap::new().push(2).push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = LeftistHeap::new();
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap = heap.push(i);
+    }
+
+    for i in 1..9 {
+        heap = {
+            let (min, rest) = heap.pop().unwrap();
+            assert_eq!(i, *min);
+
+            rest
+        };
+    }
+
+    assert!(heap.pop().is_none());
+}
+
+#[test]
+fn test_persistence() {
+    let empty = LeftistHeap::new();
+    let x = empty.push(3).push(1).push(4);
+    let y = x.push(0).push(5);
+    let (min, z) = x.pop().unwrap();
+    let merged = y.merge(&z);
+
+    assert_eq!(1, *min);
+    assert!(empty.is_empty());
+    assert_eq!(3, x.size());
+    assert_eq!(Some(&1), x.min());
+    assert_eq!(Some(&0), y.min());
+    assert_eq!(Some(&3), z.min());
+
+    let mut merged = merged;
+    assert_eq!(vec![0, 1, 3, 3, 4, 4, 5],
+               (0..7).map(|_| PriorityQueue::pop(&mut merged).unwrap()).collect::<Vec<_>>());
+
+    let mut y = y;
+    assert_eq!(vec![0, 1, 3, 4, 5],
+               (0..5).map(|_| PriorityQueue::pop(&mut y).unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    #[derive(Clone, PartialEq, PartialOrd)]
+    struct Value<'a>(Rc<DropCounter<'a>>);
+
+    let drops = Cell::new(0);
+
+    {
+        let mut x = LeftistHeap::new();
+        for i in 0..10 {
+            x = x.push(Value(Rc::new(DropCounter::new(i, &drops))));
+        }
+
+        let y = x.pop().unwrap().1.pop().unwrap().1;
+        assert_eq!(0, drops.get());
+
+        // Only the two smallest values aren't shared with `y`.
+        drop(x);
+        assert_eq!(2, drops.get());
+
+        drop(y.pop().unwrap().1);
+        assert_eq!(2, drops.get());
+    }
+
+    assert_eq!(10, drops.get());
+}
+
+#[test]
+fn test_drop_long_path() {
+    let mut heap = LeftistHeap::new();
+
+    // Every value becomes the new root with the old root as its left child.
+    for i in 0..100000 {
+        heap = heap.push(-i);
+    }
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = LeftistHeap::new();
+
+        for i in 1..10001 {
+            heap = heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap = heap.pop().unwrap().1;
+        }
+    })
+}
@@ -0,0 +1,299 @@
+// Based on "Self-Adjusting Heaps" by Daniel D. Sleator and Robert E. Tarjan.
+//
+// Unlike the mutable version, nodes are shared through `Rc` and never change. Merging copies
+// the nodes on the right paths it goes along and shares the rest, so every operation returns
+// a new heap and leaves the old one valid.
+//
+// The amortized O(log n) bound of skew heaps relies on every version being used at most once,
+// so a version that is popped or merged repeatedly may cost O(n) every time.
+
+use std::rc::Rc;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct SkewHeap<T: PartialOrd + Clone> {
+    size: usize,
+    root: Tree<T>,
+}
+
+type Tree<T> = Option<Rc<Node<T>>>;
+
+struct Node<T: PartialOrd + Clone> {
+    pub value: T,
+    pub left: Tree<T>,
+    pub right: Tree<T>,
+}
+
+impl<T: PartialOrd + Clone> SkewHeap<T> {
+    pub fn new() -> SkewHeap<T> {
+        SkewHeap {
+            size: 0,
+            root: None,
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Return a new heap with the values of this one and `value`.
+    pub fn push(&self, value: T) -> SkewHeap<T> {
+        let node = Node::new(value, None, None);
+
+        SkewHeap {
+            size: self.size + 1,
+            root: merge_trees(self.root.clone(), Some(Rc::new(node))),
+        }
+    }
+
+    /// Return the smallest value and a new heap with the rest of the values of this one.
+    pub fn pop(&self) -> Option<(&T, SkewHeap<T>)> {
+        self.root.as_ref().map(|root| {
+            let rest = SkewHeap {
+                size: self.size - 1,
+                root: merge_trees(root.left.clone(), root.right.clone()),
+            };
+
+            (&root.value, rest)
+        })
+    }
+
+    /// Return a new heap with the values of this one and `other`.
+    pub fn merge(&self, other: &SkewHeap<T>) -> SkewHeap<T> {
+        SkewHeap {
+            size: self.size + other.size,
+            root: merge_trees(self.root.clone(), other.root.clone()),
+        }
+    }
+}
+
+/// Merge the right paths of `x` and `y` into copies of their nodes with swapped children.
+fn merge_trees<T: PartialOrd + Clone>(mut x: Tree<T>, mut y: Tree<T>) -> Tree<T> {
+    let mut path = Vec::new();
+
+    let mut tree = loop {
+        match (x, y) {
+            (None, tree) | (tree, None) => break tree,
+            (Some(x_root), Some(y_root)) => {
+                let (min, max) = if y_root.value < x_root.value {
+                    (y_root, x_root)
+                } else {
+                    (x_root, y_root)
+                };
+
+                x = min.right.clone();
+                y = Some(max);
+                path.push(min);
+            }
+        }
+    };
+
+    while let Some(node) = path.pop() {
+        let copy = Node::new(node.value.clone(), tree, node.left.clone());
+        tree = Some(Rc::new(copy));
+    }
+
+    tree
+}
+
+impl<T: PartialOrd + Clone> Clone for SkewHeap<T> {
+    /// Return a heap sharing all nodes with this one in O(1) time.
+    fn clone(&self) -> SkewHeap<T> {
+        SkewHeap {
+            size: self.size,
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Default for SkewHeap<T> {
+    fn default() -> SkewHeap<T> {
+        SkewHeap::new()
+    }
+}
+
+impl<T: PartialOrd + Clone> PriorityQueue<T> for SkewHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        *self = SkewHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (value, rest) = match SkewHeap::pop(self) {
+            None => return None,
+            Some((value, rest)) => (value.clone(), rest),
+        };
+
+        *self = rest;
+        Some(value)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        SkewHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        SkewHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SkewHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd + Clone> MeldablePriorityQueue<T> for SkewHeap<T> {
+    fn merge(x: SkewHeap<T>, y: SkewHeap<T>) -> SkewHeap<T> {
+        SkewHeap::merge(&x, &y)
+    }
+}
+
+impl<T: PartialOrd + Clone> Drop for SkewHeap<T> {
+    fn drop(&mut self) {
+        // Only nodes which aren't shared with other heaps are freed, and their children are
+        // collected instead of dropped recursively.
+        let mut nodes: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(rc) = nodes.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(rc) {
+                nodes.extend(node.left.take());
+                nodes.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Node<T> {
+    pub fn new(value: T, left: Tree<T>, right: Tree<T>) -> Node<T> {
+        Node {
+            value: value,
+            left: left,
+            right: right,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let heap = SkewHeap::new().push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let heap = SkewHeap::new().push(2).push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = SkewHeap::new();
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap = heap.push(i);
+    }
+
+    for i in 1..9 {
+        heap = {
+            let (min, rest) = heap.pop().unwrap();
+            assert_eq!(i, *min);
+
+            rest
+        };
+    }
+
+    assert!(heap.pop().is_none());
+}
+
+#[test]
+fn test_persistence() {
+    let empty = SkewHeap::new();
+    let x = empty.push(3).push(1).push(4);
+    let y = x.push(0).push(5);
+    let (min, z) = x.pop().unwrap();
+    let merged = y.merge(&z);
+
+    assert_eq!(1, *min);
+    assert!(empty.is_empty());
+    assert_eq!(3, x.size());
+    assert_eq!(Some(&1), x.min());
+    assert_eq!(Some(&0), y.min());
+    assert_eq!(Some(&3), z.min());
+
+    let mut merged = merged;
+    assert_eq!(vec![0, 1, 3, 3, 4, 4, 5],
+               (0..7).map(|_| PriorityQueue::pop(&mut merged).unwrap()).collect::<Vec<_>>());
+
+    let mut y = y;
+    assert_eq!(vec![0, 1, 3, 4, 5],
+               (0..5).map(|_| PriorityQueue::pop(&mut y).unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    #[derive(Clone, PartialEq, PartialOrd)]
+    struct Value<'a>(Rc<DropCounter<'a>>);
+
+    let drops = Cell::new(0);
+
+    {
+        let mut x = SkewHeap::new();
+        for i in 0..10 {
+            x = x.push(Value(Rc::new(DropCounter::new(i, &drops))));
+        }
+
+        let y = x.pop().unwrap().1.pop().unwrap().1;
+        assert_eq!(0, drops.get());
+
+        // Only the two smallest values aren't shared with `y`.
+        drop(x);
+        assert_eq!(2, drops.get());
+
+        drop(y.pop().unwrap().1);
+        assert_eq!(2, drops.get());
+    }
+
+    assert_eq!(10, drops.get());
+}
+
+#[test]
+fn test_drop_long_path() {
+    let mut heap = SkewHeap::new();
+
+    // Every value is merged into the left path of the root.
+    for i in 0..100000 {
+        heap = heap.push(i);
+    }
+
+    for _ in 0..10 {
+        heap = heap.pop().unwrap().1;
+    }
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = SkewHeap::new();
+
+        for i in 1..10001 {
+            heap = heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap = heap.pop().unwrap().1;
+        }
+    })
+}
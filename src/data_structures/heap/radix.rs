@@ -0,0 +1,261 @@
+// Based on "Faster Algorithms for the Shortest Path Problem" by Ravindra K. Ahuja, Kurt
+// Mehlhorn, James B. Orlin and Robert E. Tarjan.
+//
+// Keys can't be smaller than the last popped key. Entries are kept in buckets by the highest
+// bit in which their key differs from the last popped one, so bucket `i` holds keys which
+// share all bits above bit `i - 1` with it. Pop moves the entries of the first non-empty
+// bucket to lower buckets, and every entry can only move down `K::bits()` times, which makes
+// push O(1) and pop O(K::bits()) amortized.
+//
+// Entries with equal keys are always in the same bucket, in the order they were pushed, since
+// buckets are only appended to. Popping bucket 0 from the front keeps the heap stable.
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::mem;
+
+use super::PriorityQueue;
+
+/// Trait encompassing unsigned integers usable as keys of a `RadixHeap`.
+pub trait RadixKey: Copy + Ord + Display {
+    /// Return the equivalent of `0` for this type.
+    fn zero() -> Self;
+
+    /// Return the number of bits of this type.
+    fn bits() -> usize;
+
+    /// Return the position of the highest bit in which `self` and `other` differ plus one,
+    /// or `0` if they're equal.
+    fn diff_bits(self, other: Self) -> usize;
+}
+
+macro_rules! impl_radix_key {
+    ($($ty:ty),*) => (
+        $(impl RadixKey for $ty {
+            fn zero() -> Self {
+                0 as $ty
+            }
+
+            fn bits() -> usize {
+                mem::size_of::<$ty>() * 8
+            }
+
+            #[inline]
+            fn diff_bits(self, other: Self) -> usize {
+                Self::bits() - (self ^ other).leading_zeros() as usize
+            }
+        })*
+    )
+}
+
+impl_radix_key! { u8, u16, u32, u64, usize }
+
+/// Heap of values with unsigned integer keys, where keys pushed can't be smaller than the last
+/// popped key. Values with equal keys are popped in the order they were pushed.
+pub struct RadixHeap<K: RadixKey, V> {
+    size: usize,
+    last: K,
+    buckets: Vec<VecDeque<(K, V)>>,
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    pub fn new() -> RadixHeap<K, V> {
+        RadixHeap {
+            size: 0,
+            last: K::zero(),
+            buckets: (0..K::bits() + 1).map(|_| VecDeque::new()).collect(),
+        }
+    }
+
+    /// Return the smallest key with its value.
+    ///
+    /// Unless a value with the last popped key is left, this has to look through a bucket,
+    /// so it takes O(n) time in the worst case.
+    pub fn min(&self) -> Option<(K, &V)> {
+        self.min_entry().map(|&(key, ref value)| (key, value))
+    }
+
+    /// Return the last popped key, which is the smallest key that can be pushed.
+    pub fn last_key(&self) -> K {
+        self.last
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Add `value` with `key` to the heap.
+    ///
+    /// ##Panics
+    /// If `key` is smaller than the last popped key.
+    pub fn push(&mut self, key: K, value: V) {
+        assert!(key >= self.last,
+                "Called push with key = {} smaller than the last popped key = {}",
+                key,
+                self.last);
+
+        self.buckets[key.diff_bits(self.last)].push_back((key, value));
+        self.size += 1;
+    }
+
+    /// Remove the value with the smallest key from the heap and return it with its key.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            let index = match self.buckets.iter().position(|bucket| !bucket.is_empty()) {
+                None => return None,
+                Some(index) => index,
+            };
+
+            let mut bucket = mem::replace(&mut self.buckets[index], VecDeque::new());
+            self.last = bucket.iter().map(|entry| entry.0).min().unwrap();
+
+            for entry in bucket.drain(..) {
+                self.buckets[entry.0.diff_bits(self.last)].push_back(entry);
+            }
+
+            // Keep the allocation of the emptied bucket.
+            self.buckets[index] = bucket;
+        }
+
+        self.size -= 1;
+        self.buckets[0].pop_front()
+    }
+
+    fn min_entry(&self) -> Option<&(K, V)> {
+        self.buckets
+            .iter()
+            .find(|bucket| !bucket.is_empty())
+            .and_then(|bucket| bucket.iter().min_by_key(|entry| entry.0))
+    }
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> RadixHeap<K, V> {
+        RadixHeap::new()
+    }
+}
+
+impl<K: RadixKey, V> PriorityQueue<(K, V)> for RadixHeap<K, V> {
+    type Ref<'a> = &'a (K, V) where Self: 'a;
+
+    fn push(&mut self, entry: (K, V)) {
+        RadixHeap::push(self, entry.0, entry.1)
+    }
+
+    fn pop(&mut self) -> Option<(K, V)> {
+        RadixHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a (K, V)> {
+        RadixHeap::min_entry(self)
+    }
+
+    fn size(&self) -> usize {
+        RadixHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        RadixHeap::is_empty(self)
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = RadixHeap::new();
+
+    heap.push(1u32, ());
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let mut heap = RadixHeap::new();
+
+    heap.push(5u32, "b");
+    heap.push(3, "a");
+
+    assert_eq!(Some((3, &"a")), heap.min());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = RadixHeap::new();
+
+    for &i in &[7u8, 1, 8, 4, 5, 2, 3, 6, 255, 0] {
+        heap.push(i, i as i32 * 10);
+    }
+
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 255],
+               (0..10).map(|_| heap.pop().unwrap().0).collect::<Vec<_>>());
+    assert!(heap.pop().is_none());
+}
+
+#[test]
+fn test_monotone() {
+    let mut heap = RadixHeap::new();
+    let mut popped = Vec::new();
+
+    heap.push(10u64, 'a');
+    heap.push(20, 'b');
+
+    popped.push(heap.pop().unwrap());
+    assert_eq!(10, heap.last_key());
+
+    // Keys equal to the last popped key can still be pushed.
+    heap.push(10, 'c');
+    heap.push(15, 'd');
+
+    while let Some(entry) = heap.pop() {
+        popped.push(entry);
+    }
+
+    assert_eq!(vec![(10, 'a'), (10, 'c'), (15, 'd'), (20, 'b')], popped);
+}
+
+#[test]
+fn test_stable() {
+    let mut heap = RadixHeap::new();
+
+    for (i, name) in "abcdefghij".chars().enumerate() {
+        heap.push(i as u32 % 3 * 4, name);
+    }
+
+    heap.pop();
+    heap.push(0, 'k');
+    heap.push(8, 'l');
+
+    let popped: String = (0..11).map(|_| heap.pop().unwrap().1).collect();
+    assert_eq!("dgjkbehcfil", popped);
+}
+
+#[test]
+#[should_panic]
+fn test_push_smaller_than_last() {
+    let mut heap = RadixHeap::new();
+
+    heap.push(10usize, ());
+    heap.push(20, ());
+    heap.pop();
+
+    heap.push(9, ());
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = RadixHeap::new();
+
+        for i in 1..10001u32 {
+            heap.push(10001 - i, ());
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
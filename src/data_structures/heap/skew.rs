@@ -0,0 +1,246 @@
+// Based on "Self-Adjusting Heaps" by Daniel D. Sleator and Robert E. Tarjan.
+//
+// Like a leftist heap without ranks: merging goes along the right paths and swaps the children
+// of every node on the way, which makes push, pop and merge O(log n) amortized.
+
+use std::mem;
+
+use super::{PriorityQueue, MeldablePriorityQueue};
+
+pub struct SkewHeap<T: PartialOrd> {
+    size: usize,
+    root: Tree<T>,
+}
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+struct Node<T: PartialOrd> {
+    pub value: T,
+    pub left: Tree<T>,
+    pub right: Tree<T>,
+}
+
+impl<T: PartialOrd> SkewHeap<T> {
+    pub fn new() -> SkewHeap<T> {
+        SkewHeap {
+            size: 0,
+            root: None,
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn push(&mut self, value: T) {
+        let root = self.root.take();
+        self.root = merge_trees(root, Some(Box::new(Node::new(value))));
+        self.size += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.root.take().map(|mut root| {
+            self.root = merge_trees(root.left.take(), root.right.take());
+            self.size -= 1;
+
+            root.value
+        })
+    }
+
+    pub fn merge(mut x: SkewHeap<T>, mut y: SkewHeap<T>) -> SkewHeap<T> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, leaving `other` empty.
+    pub fn append(&mut self, other: &mut SkewHeap<T>) {
+        let root = self.root.take();
+        self.root = merge_trees(root, other.root.take());
+        self.size += mem::replace(&mut other.size, 0);
+    }
+}
+
+/// Merge the right paths of `x` and `y` and swap the children of the nodes on the merged path.
+/// Merging is done in a loop instead of recursively, and so are the other traversals, since
+/// paths may have O(n) nodes.
+fn merge_trees<T: PartialOrd>(mut x: Tree<T>, mut y: Tree<T>) -> Tree<T> {
+    let mut path = Vec::new();
+
+    let mut tree = loop {
+        match (x, y) {
+            (None, tree) | (tree, None) => break tree,
+            (Some(x_root), Some(y_root)) => {
+                let (mut min, max) = if y_root.value < x_root.value {
+                    (y_root, x_root)
+                } else {
+                    (x_root, y_root)
+                };
+
+                x = min.right.take();
+                y = Some(max);
+                path.push(min);
+            }
+        }
+    };
+
+    while let Some(mut node) = path.pop() {
+        node.right = node.left.take();
+        node.left = tree;
+        tree = Some(node);
+    }
+
+    tree
+}
+
+impl<T: PartialOrd> Default for SkewHeap<T> {
+    fn default() -> SkewHeap<T> {
+        SkewHeap::new()
+    }
+}
+
+impl<T: PartialOrd> PriorityQueue<T> for SkewHeap<T> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        SkewHeap::push(self, value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        SkewHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        SkewHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        SkewHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SkewHeap::is_empty(self)
+    }
+}
+
+impl<T: PartialOrd> MeldablePriorityQueue<T> for SkewHeap<T> {
+    fn merge(x: SkewHeap<T>, y: SkewHeap<T>) -> SkewHeap<T> {
+        SkewHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut SkewHeap<T>) {
+        SkewHeap::append(self, other)
+    }
+}
+
+impl<T: PartialOrd> Drop for SkewHeap<T> {
+    fn drop(&mut self) {
+        let mut nodes: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = nodes.pop() {
+            nodes.extend(node.left.take());
+            nodes.extend(node.right.take());
+        }
+    }
+}
+
+impl<T: PartialOrd> Node<T> {
+    pub fn new(value: T) -> Node<T> {
+        Node {
+            value: value,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+#[test]
+fn test_size() {
+    let mut heap = SkewHeap::new();
+
+    heap.push(1);
+
+    assert_eq!(1, heap.size());
+}
+
+#[test]
+fn test_min() {
+    let mut heap = SkewHeap::new();
+
+    heap.push(2);
+    heap.push(1);
+
+    assert_eq!(1, *heap.min().unwrap());
+}
+
+#[test]
+fn test_order() {
+    let mut heap = SkewHeap::new();
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.pop().is_none());
+}
+
+#[test]
+fn test_merge() {
+    let mut x = SkewHeap::new();
+    let mut y = SkewHeap::new();
+
+    for i in 0..7 {
+        x.push(i * 2);
+    }
+
+    for i in 0..5 {
+        y.push(i * 2 + 1);
+    }
+
+    y.pop();
+
+    let mut merged = SkewHeap::merge(x, y);
+
+    assert_eq!(11, merged.size());
+    assert_eq!(vec![0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12],
+               (0..11).map(|_| merged.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop_long_path() {
+    let mut heap = SkewHeap::new();
+
+    // Every value is merged into the left path of the root.
+    for i in 0..100000 {
+        heap.push(i);
+    }
+
+    for _ in 0..10 {
+        heap.pop();
+    }
+}
+
+#[bench]
+fn bench_push_pop(b: &mut ::test::Bencher) {
+    b.iter(|| {
+        let mut heap = SkewHeap::new();
+
+        for i in 1..10001 {
+            heap.push(10001 - i);
+        }
+
+        for _ in 1..10001 {
+            heap.pop();
+        }
+    })
+}
@@ -0,0 +1,351 @@
+// Stable ordering for any heap of this module. Every value is pushed with the number of pushes
+// before it, and values that compare equal are ordered by that number, so they're popped in
+// the order they were pushed.
+
+use std::cmp::Ordering;
+use std::ops::Deref;
+
+use super::{PriorityQueue, MeldablePriorityQueue, AddressablePriorityQueue, Compare};
+
+/// Value with the sequence number of its push, which breaks ties between equal values.
+#[derive(Clone, Copy, Debug)]
+pub struct Stamped<T> {
+    pub value: T,
+    pub sequence: u64,
+}
+
+/// Ordering of stamped values by another ordering, and by their sequence numbers if it
+/// considers them equal.
+///
+/// `Stamped` values are ordered that way with `PartialOrd`, so this is only needed by heaps
+/// with an ordering other than `MinOrder`, like `StableOrder::new(MaxOrder)`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StableOrder<C> {
+    compare: C,
+}
+
+/// Heap adapter which pops equal values in the order they were pushed.
+///
+/// `H` is any heap of `Stamped` values, like `Stable<FibonacciHeap<Stamped<T>>>`, and the
+/// adapter implements the heap traits of `H` for the values themselves. Values with a
+/// decreased key keep the sequence numbers of their pushes, which their handles hold, so
+/// equal values are popped in the order they were pushed however they got equal, and a key
+/// can be decreased to an equal value.
+///
+/// Some queues don't need the adapter:
+///
+/// - `RadixHeap` and `IndexedMinPQ` are stable on their own.
+/// - `MultiQueue` can't be stable. It pops from random heaps to avoid a total order of its
+///   values, so a value pushed later can be popped first whatever its sequence number.
+pub struct Stable<H> {
+    heap: H,
+    sequence: u64,
+}
+
+/// Borrowed smallest value of a `Stable` heap.
+pub struct StableRef<R> {
+    stamped: R,
+}
+
+/// Handle to a value pushed into a `Stable` heap, with the sequence number of its push.
+#[derive(Clone, Copy, Debug)]
+pub struct StableHandle<K> {
+    handle: K,
+    sequence: u64,
+}
+
+impl<T> Stamped<T> {
+    pub fn new(value: T, sequence: u64) -> Stamped<T> {
+        Stamped {
+            value: value,
+            sequence: sequence,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Stamped<T> {
+    fn eq(&self, other: &Stamped<T>) -> bool {
+        self.value == other.value && self.sequence == other.sequence
+    }
+}
+
+impl<T: Eq> Eq for Stamped<T> {}
+
+impl<T: PartialOrd> PartialOrd for Stamped<T> {
+    fn partial_cmp(&self, other: &Stamped<T>) -> Option<Ordering> {
+        match self.value.partial_cmp(&other.value) {
+            Some(Ordering::Equal) => Some(self.sequence.cmp(&other.sequence)),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<T: Ord> Ord for Stamped<T> {
+    fn cmp(&self, other: &Stamped<T>) -> Ordering {
+        self.value.cmp(&other.value).then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+impl<C> StableOrder<C> {
+    pub fn new(compare: C) -> StableOrder<C> {
+        StableOrder { compare: compare }
+    }
+}
+
+impl<T, C: Compare<T>> Compare<Stamped<T>> for StableOrder<C> {
+    fn compare(&self, x: &Stamped<T>, y: &Stamped<T>) -> Ordering {
+        self.compare.compare(&x.value, &y.value).then(x.sequence.cmp(&y.sequence))
+    }
+}
+
+impl<H> Stable<H> {
+    /// Wrap an empty `heap`.
+    pub fn new(heap: H) -> Stable<H> {
+        Stable {
+            heap: heap,
+            sequence: 0,
+        }
+    }
+
+    /// Return the wrapped heap.
+    pub fn get_ref(&self) -> &H {
+        &self.heap
+    }
+
+    /// Unwrap the heap.
+    pub fn into_inner(self) -> H {
+        self.heap
+    }
+
+    fn stamp<T>(&mut self, value: T) -> Stamped<T> {
+        let stamped = Stamped::new(value, self.sequence);
+        self.sequence += 1;
+
+        stamped
+    }
+}
+
+impl<H: Default> Default for Stable<H> {
+    fn default() -> Stable<H> {
+        Stable::new(H::default())
+    }
+}
+
+impl<T, H: PriorityQueue<Stamped<T>>> PriorityQueue<T> for Stable<H> {
+    type Ref<'a> = StableRef<H::Ref<'a>> where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        let stamped = self.stamp(value);
+        self.heap.push(stamped)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|stamped| stamped.value)
+    }
+
+    fn min<'a>(&'a self) -> Option<StableRef<H::Ref<'a>>> {
+        self.heap.min().map(|stamped| StableRef { stamped: stamped })
+    }
+
+    fn size(&self) -> usize {
+        self.heap.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T, H: MeldablePriorityQueue<Stamped<T>>> MeldablePriorityQueue<T> for Stable<H> {
+    /// Merge two heaps into one. Equal values of different heaps are ordered by their sequence
+    /// numbers, which only follow the order of pushes within each heap.
+    fn merge(x: Stable<H>, y: Stable<H>) -> Stable<H> {
+        Stable {
+            heap: H::merge(x.heap, y.heap),
+            sequence: if x.sequence < y.sequence {
+                y.sequence
+            } else {
+                x.sequence
+            },
+        }
+    }
+}
+
+impl<T, H: AddressablePriorityQueue<Stamped<T>>> AddressablePriorityQueue<T> for Stable<H> {
+    type Handle = StableHandle<H::Handle>;
+
+    fn push_with_handle(&mut self, value: T) -> StableHandle<H::Handle> {
+        let stamped = self.stamp(value);
+        let sequence = stamped.sequence;
+
+        StableHandle {
+            handle: self.heap.push_with_handle(stamped),
+            sequence: sequence,
+        }
+    }
+
+    fn decrease_key(&mut self, handle: &StableHandle<H::Handle>, value: T) {
+        let stamped = Stamped::new(value, handle.sequence);
+        self.heap.decrease_key(&handle.handle, stamped)
+    }
+
+    fn delete(&mut self, handle: &StableHandle<H::Handle>) -> T {
+        self.heap.delete(&handle.handle).value
+    }
+}
+
+impl<T, R: Deref<Target = Stamped<T>>> Deref for StableRef<R> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.stamped.value
+    }
+}
+
+/// Event which is ordered by its time only.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug)]
+struct Event(i32, char);
+
+#[cfg(test)]
+impl PartialEq for Event {
+    fn eq(&self, other: &Event) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(test)]
+impl Eq for Event {}
+
+#[cfg(test)]
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Event) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+impl Ord for Event {
+    fn cmp(&self, other: &Event) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+fn check_stable<H: PriorityQueue<Stamped<Event>> + Default>() {
+    let mut heap = Stable::<H>::default();
+
+    let names = "abcdefghijklmnopqrstuvwxyz";
+    for (i, name) in names.chars().enumerate() {
+        heap.push(Event(i as i32 % 3, name));
+
+        // Pop from time to time, so the heaps have to restructure.
+        if i % 7 == 6 {
+            heap.pop();
+        }
+    }
+
+    assert_eq!('j', heap.min().unwrap().1);
+
+    let popped: String = (0..heap.size()).map(|_| heap.pop().unwrap().1).collect();
+    assert_eq!("jmpsvybehknqtwzcfilorux", popped);
+}
+
+#[test]
+fn test_stable() {
+    use super::*;
+
+    check_stable::<BinaryHeap<Stamped<Event>>>();
+    check_stable::<BinomialHeap<Stamped<Event>>>();
+    check_stable::<LazyBinomialHeap<Stamped<Event>>>();
+    check_stable::<DaryHeap<Stamped<Event>>>();
+    check_stable::<MinMaxHeap<Stamped<Event>>>();
+    check_stable::<FibonacciHeap<Stamped<Event>>>();
+    check_stable::<UnsafeFibonacciHeap<Stamped<Event>>>();
+    check_stable::<ArenaFibonacciHeap<Stamped<Event>>>();
+    check_stable::<PairingHeap<Stamped<Event>>>();
+    check_stable::<LeftistHeap<Stamped<Event>>>();
+    check_stable::<PersistentLeftistHeap<Stamped<Event>>>();
+    check_stable::<SkewHeap<Stamped<Event>>>();
+    check_stable::<PersistentSkewHeap<Stamped<Event>>>();
+}
+
+#[test]
+fn test_radix_fifo() {
+    use super::RadixHeap;
+
+    let mut heap = RadixHeap::new();
+    let mut popped = Vec::new();
+
+    // Keys of the block `i / 10` are at least `i / 10`, so smaller ones can be popped in
+    // between.
+    for i in 0..100u32 {
+        heap.push(i / 10 + i % 3, i);
+
+        while heap.min().map_or(false, |(key, _)| key <= i / 10) {
+            popped.extend(heap.pop());
+        }
+    }
+
+    popped.extend((0..heap.size()).map(|_| heap.pop().unwrap()));
+
+    // Values with equal keys come out in the order they were pushed.
+    for pair in popped.windows(2) {
+        assert!(pair[0].0 < pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1);
+    }
+}
+
+#[test]
+fn test_stable_order() {
+    use super::{FibonacciHeap, MaxOrder};
+
+    let mut heap = Stable::new(FibonacciHeap::with_comparator(StableOrder::new(MaxOrder)));
+
+    for (i, name) in "abcdef".chars().enumerate() {
+        heap.push(Event(i as i32 % 2, name));
+    }
+
+    let popped: String = (0..6).map(|_| heap.pop().unwrap().1).collect();
+    assert_eq!("bdface", popped);
+}
+
+#[test]
+fn test_stable_decrease_key() {
+    use super::PairingHeap;
+
+    let mut heap = Stable::new(PairingHeap::new());
+
+    let a = heap.push_with_handle(Event(5, 'a'));
+    heap.push_with_handle(Event(1, 'b'));
+    heap.push_with_handle(Event(5, 'c'));
+    let d = heap.push_with_handle(Event(3, 'd'));
+
+    // `a` was pushed before `b`, so it's popped first once they're equal.
+    heap.decrease_key(&a, Event(1, 'a'));
+    assert_eq!('d', heap.delete(&d).1);
+
+    let popped: String = (0..3).map(|_| heap.pop().unwrap().1).collect();
+    assert_eq!("abc", popped);
+}
+
+#[test]
+fn test_stable_decrease_key_equal() {
+    use super::{PairingHeap, FibonacciHeap, DaryHeap};
+
+    fn check<H: AddressablePriorityQueue<Stamped<Event>>>(heap: H) {
+        let mut heap = Stable::new(heap);
+
+        let a = heap.push_with_handle(Event(5, 'a'));
+        let b = heap.push_with_handle(Event(5, 'b'));
+
+        heap.decrease_key(&b, Event(5, 'b'));
+        heap.decrease_key(&a, Event(5, 'a'));
+
+        let popped: String = (0..2).map(|_| heap.pop().unwrap().1).collect();
+        assert_eq!("ab", popped);
+    }
+
+    check(PairingHeap::new());
+    check(FibonacciHeap::new());
+    check(DaryHeap::new());
+}
@@ -1,39 +1,62 @@
 // Based on http://www.keithschwarz.com/interesting/code/?dir=fibonacci-heap
 
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
-use std::cmp::Ordering;
+use std::vec;
 
-pub struct FibonacciHeap<T: PartialOrd> {
+use super::{PriorityQueue, MeldablePriorityQueue};
+use super::{Compare, MinOrder};
+
+pub struct FibonacciHeap<T, C = MinOrder> {
     size: usize,
     min: Link<T>,
     tree_table: Vec<Link<T>>,
     to_visit: Vec<Link<T>>,
+    compare: C,
 }
 
-struct Entry<T: PartialOrd> {
+struct Entry<T> {
     pub value: T,
 
     pub degree: usize,
-    // TODO: Implement decrease_key and delete
-    // pub is_marked: bool,
+    pub is_marked: bool,
     pub parent: Link<T>,
     pub next: Link<T>,
     pub prev: Link<T>,
     pub child: Link<T>,
 }
 
-struct Link<T: PartialOrd> {
+struct Link<T> {
+    entry: *mut Entry<T>,
+}
+
+/// Stable reference to a value pushed into a `FibonacciHeap`.
+///
+/// The handle is a plain pointer to the entry, so it becomes dangling once the value
+/// is popped or deleted. That's why the methods accepting it are `unsafe`.
+pub struct Handle<T> {
     entry: *mut Entry<T>,
 }
 
 impl<T: PartialOrd> FibonacciHeap<T> {
     pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap::with_comparator(MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Create a heap that pops values in the order given by `compare`, smallest first.
+    pub fn with_comparator(compare: C) -> FibonacciHeap<T, C> {
         FibonacciHeap {
             min: Link::none(),
             size: 0,
             to_visit: Vec::new(),
             tree_table: Vec::new(),
+            compare: compare,
         }
     }
 
@@ -49,14 +72,61 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         self.size
     }
 
-    pub fn push(&mut self, value: T) {
+    pub fn push(&mut self, value: T) -> Handle<T> {
         let link = Link::new(value);
+        let handle = Handle { entry: link.entry };
 
         let mut min = Link::none();
         mem::swap(&mut min, &mut self.min);
 
-        self.min = FibonacciHeap::<T>::merge_entries(min, link);
+        self.min = self.merge_entries(min, link);
         self.size += 1;
+
+        handle
+    }
+
+    /// Replace the value referenced by `handle` with a smaller `value`.
+    ///
+    /// # Safety
+    /// `handle` must be returned by `push` of this heap and its value must still be in the heap.
+    ///
+    /// ##Panics
+    /// If `value` is greater than the current one.
+    pub unsafe fn decrease_key(&mut self, handle: &Handle<T>, value: T) {
+        let mut link = Link { entry: handle.entry };
+
+        assert!(!self.compare.less(link.borrow().unwrap(), &value),
+                "Called decrease_key with a value greater than the current one");
+
+        link.set_value(value);
+
+        let parent = link.get_parent();
+        if parent.is_some() && self.less(&link, &parent) {
+            self.cut(link.clone(), parent.clone());
+            self.cascading_cut(parent);
+        }
+
+        if self.less(&link, &self.min) {
+            self.min = link;
+        }
+    }
+
+    /// Remove the value referenced by `handle` from the heap and return it.
+    ///
+    /// # Safety
+    /// `handle` must be returned by `push` of this heap and its value must still be in the heap.
+    pub unsafe fn delete(&mut self, handle: &Handle<T>) -> T {
+        let link = Link { entry: handle.entry };
+
+        let parent = link.get_parent();
+        if parent.is_some() {
+            self.cut(link.clone(), parent.clone());
+            self.cascading_cut(parent);
+        }
+
+        // Pretend the entry is the smallest one, so pop removes it and consolidates the roots.
+        self.min = link;
+        self.pop().unwrap()
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -91,7 +161,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         let mut new_min = Link::none();
         mem::swap(&mut new_min, &mut self.min);
 
-        self.min = FibonacciHeap::<T>::merge_entries(new_min, min_child);
+        self.min = self.merge_entries(new_min, min_child);
 
         if self.size == 0 {
             return min.into_value();
@@ -122,7 +192,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
                 self.tree_table.push(Link::none());
                 let other = self.tree_table.swap_remove(link_degree);
 
-                let (mut min_link, mut max) = if link < other {
+                let (mut min_link, mut max) = if self.less(&link, &other) {
                     (link, other)
                 } else {
                     (other, link)
@@ -138,17 +208,17 @@ impl<T: PartialOrd> FibonacciHeap<T> {
                 max.set_next(&max_clone);
 
                 max.set_parent(&min_link);
+                max.set_marked(false);
 
                 let min_link_child = min_link.get_child();
-                min_link.set_child(&FibonacciHeap::<T>::merge_entries(min_link_child, max));
-                // max.is_marked = false;
+                min_link.set_child(&self.merge_entries(min_link_child, max));
 
                 min_link.inc_degree();
 
                 link = min_link;
             }
 
-            if link <= self.min {
+            if !self.less(&self.min, &link) {
                 self.min = link;
             }
         }
@@ -159,16 +229,169 @@ impl<T: PartialOrd> FibonacciHeap<T> {
         min.into_value()
     }
 
-    pub fn merge(x: FibonacciHeap<T>, y: FibonacciHeap<T>) -> FibonacciHeap<T> {
-        let mut result = FibonacciHeap::new();
+    /// Merge two heaps into one, which keeps the ordering of `x`.
+    pub fn merge(mut x: FibonacciHeap<T, C>, mut y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        x.append(&mut y);
+
+        x
+    }
+
+    /// Move all values of `other` into this heap, which keeps its ordering, and leave `other`
+    /// empty.
+    ///
+    /// The root lists are spliced in O(1) time. This heap takes ownership of the entries of
+    /// `other`, so handles returned by `other` now refer to values of this heap and must only
+    /// be used with it.
+    pub fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        let mut min = Link::none();
+        mem::swap(&mut min, &mut self.min);
+
+        let mut other_min = Link::none();
+        mem::swap(&mut other_min, &mut other.min);
+
+        self.min = self.merge_entries(min, other_min);
+        self.size += other.size;
+        other.size = 0;
+    }
+
+    /// Remove all values from the heap and return them from the smallest to the largest.
+    ///
+    /// Values are popped one by one, so the heap is left empty even if the iterator is
+    /// dropped early.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C> {
+        DrainSorted { heap: self }
+    }
+
+    /// Panic if the structure of the heap is broken: a value is smaller than its parent or
+    /// the minimum, an entry points at a wrong parent, a sibling list isn't a consistent ring,
+    /// a degree doesn't match the number of children, or the number of entries isn't `size`.
+    pub fn check_invariants(&self) {
+        let mut count = 0;
+        let mut rings = vec![(self.min.clone(), Link::none())];
+
+        while let Some((start, parent)) = rings.pop() {
+            if start.is_none() {
+                continue;
+            }
+
+            let mut current = start.clone();
+            let mut ring_len = 0;
+
+            loop {
+                count += 1;
+                ring_len += 1;
+                assert!(count <= self.size,
+                        "Invariant violated: more entries than size = {}",
+                        self.size);
+
+                assert!(current.get_parent().are_same(&parent),
+                        "Invariant violated: entry points at a wrong parent");
+                assert!(current.get_next().get_prev().are_same(&current) &&
+                        current.get_prev().get_next().are_same(&current),
+                        "Invariant violated: broken sibling ring");
+
+                if parent.is_none() {
+                    assert!(!self.less(&current, &self.min),
+                            "Invariant violated: root is smaller than the minimum");
+                } else {
+                    assert!(!self.less(&current, &parent),
+                            "Invariant violated: entry is smaller than its parent");
+                }
+
+                let child = current.get_child();
+                if child.is_none() {
+                    assert!(current.get_degree() == 0,
+                            "Invariant violated: degree of an entry without children isn't 0");
+                } else {
+                    rings.push((child, current.clone()));
+                }
+
+                current = current.get_next();
+                if current.are_same(&start) {
+                    break;
+                }
+            }
+
+            if !parent.is_none() {
+                assert!(parent.get_degree() == ring_len,
+                        "Invariant violated: degree = {} of an entry with {} children",
+                        parent.get_degree(),
+                        ring_len);
+            }
+        }
+
+        assert!(count == self.size,
+                "Invariant violated: {} entries, but size = {}",
+                count,
+                self.size);
+    }
+
+    /// Consume the heap and return its values from the smallest to the largest.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.size);
+
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+
+        values
+    }
+
+    fn cut(&mut self, mut link: Link<T>, mut parent: Link<T>) {
+        let mut next = link.get_next();
+
+        if next.are_same(&link) {
+            parent.set_child(&Link::none());
+        } else {
+            let mut prev = link.get_prev();
+            prev.set_next(&next);
+            next.set_prev(&prev);
+
+            if parent.get_child().are_same(&link) {
+                parent.set_child(&next);
+            }
+
+            let link_clone = link.clone();
+            link.set_prev(&link_clone);
+            link.set_next(&link_clone);
+        }
+
+        parent.dec_degree();
+        link.set_parent(&Link::none());
+        link.set_marked(false);
+
+        let mut min = Link::none();
+        mem::swap(&mut min, &mut self.min);
+
+        self.min = self.merge_entries(min, link);
+    }
+
+    fn cascading_cut(&mut self, mut link: Link<T>) {
+        loop {
+            let parent = link.get_parent();
+            if parent.is_none() {
+                break;
+            }
+
+            if !link.is_marked() {
+                link.set_marked(true);
+                break;
+            }
 
-        result.min = FibonacciHeap::<T>::merge_entries(x.min, y.min);
-        result.size = x.size + y.size;
+            self.cut(link, parent.clone());
+            link = parent;
+        }
+    }
 
-        result
+    #[inline]
+    fn less(&self, x: &Link<T>, y: &Link<T>) -> bool {
+        match (x.borrow(), y.borrow()) {
+            (Some(x), Some(y)) => self.compare.less(x, y),
+            _ => false,
+        }
     }
 
-    fn merge_entries(mut x: Link<T>, mut y: Link<T>) -> Link<T> {
+    fn merge_entries(&self, mut x: Link<T>, mut y: Link<T>) -> Link<T> {
         if x.is_none() && y.is_none() {
             Link::none()
         } else if !x.is_none() && y.is_none() {
@@ -183,7 +406,7 @@ impl<T: PartialOrd> FibonacciHeap<T> {
             y.set_next(&x_next);
             x_next.set_prev(&y);
 
-            if x < y {
+            if self.less(&x, &y) {
                 x
             } else {
                 y
@@ -192,13 +415,338 @@ impl<T: PartialOrd> FibonacciHeap<T> {
     }
 }
 
-impl<T: PartialOrd> Entry<T> {
+impl<T, C> FibonacciHeap<T, C> {
+    /// Return an iterator over the values of the heap in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            entries: Entries::new(&self.min),
+            len: self.size,
+            marker: PhantomData,
+        }
+    }
+
+    /// Render the forest as a Graphviz digraph with an edge from every entry to each of its
+    /// children. The minimum has a double border and marked entries are dashed.
+    pub fn to_dot(&self) -> String
+        where T: fmt::Debug
+    {
+        let mut dot = String::from("digraph {\n");
+        let mut id = 0;
+        let mut rings = vec![(self.min.clone(), None)];
+
+        while let Some((start, parent)) = rings.pop() {
+            if start.is_none() {
+                continue;
+            }
+
+            let mut current = start.clone();
+
+            loop {
+                let label = format!("{:?}", *current.borrow().unwrap())
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"");
+                let style = if current.are_same(&self.min) {
+                    ", peripheries=2"
+                } else if current.is_marked() {
+                    ", style=dashed"
+                } else {
+                    ""
+                };
+
+                dot.push_str(&format!("    n{} [label=\"{}\"{}];\n", id, label, style));
+                if let Some(parent) = parent {
+                    dot.push_str(&format!("    n{} -> n{};\n", parent, id));
+                }
+
+                rings.push((current.get_child(), Some(id)));
+                id += 1;
+
+                current = current.get_next();
+                if current.are_same(&start) {
+                    break;
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Remove all values from the heap.
+    pub fn clear(&mut self) {
+        let min = mem::replace(&mut self.min, Link::none());
+        self.size = 0;
+
+        for link in Entries::new(&min) {
+            link.into_value();
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for FibonacciHeap<T, C> {
+    fn default() -> FibonacciHeap<T, C> {
+        FibonacciHeap::with_comparator(C::default())
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for FibonacciHeap<T, C> {
+    /// Copy the heap with the same tree structure. Handles to the values of the original heap
+    /// must not be used with the copy.
+    fn clone(&self) -> FibonacciHeap<T, C> {
+        let mut copies = HashMap::new();
+
+        for link in Entries::new(&self.min) {
+            let entry = unsafe { &*link.entry };
+
+            let mut copy = Entry::new(entry.value.clone());
+            copy.degree = entry.degree;
+            copy.is_marked = entry.is_marked;
+
+            copies.insert(link.entry, Box::into_raw(Box::new(copy)));
+        }
+
+        let copy_link = |link: &Link<T>| if link.is_none() {
+            Link::none()
+        } else {
+            Link { entry: copies[&link.entry] }
+        };
+
+        for (&entry, &copy) in &copies {
+            unsafe {
+                (*copy).parent = copy_link(&(*entry).parent);
+                (*copy).next = copy_link(&(*entry).next);
+                (*copy).prev = copy_link(&(*entry).prev);
+                (*copy).child = copy_link(&(*entry).child);
+            }
+        }
+
+        FibonacciHeap {
+            size: self.size,
+            min: copy_link(&self.min),
+            tree_table: Vec::new(),
+            to_visit: Vec::new(),
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for FibonacciHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for FibonacciHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> FibonacciHeap<T, C> {
+        let mut heap = FibonacciHeap::default();
+        heap.extend(iter);
+
+        heap
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, C> IntoIterator for FibonacciHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consume the heap and return an iterator over its values in arbitrary order.
+    fn into_iter(mut self) -> IntoIter<T> {
+        let min = mem::replace(&mut self.min, Link::none());
+        self.size = 0;
+
+        let values: Vec<_> = Entries::new(&min).filter_map(|link| link.into_value()).collect();
+
+        IntoIter { values: values.into_iter() }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a FibonacciHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over borrowed values of a `FibonacciHeap` in arbitrary order.
+pub struct Iter<'a, T: 'a> {
+    entries: Entries<T>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.entries.next().map(|link| {
+            self.len -= 1;
+            unsafe { &(*link.entry).value }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Iterator over the values of a consumed `FibonacciHeap` in arbitrary order.
+pub struct IntoIter<T> {
+    values: vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.values.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Iterator popping the values of a `FibonacciHeap` from the smallest to the largest.
+pub struct DrainSorted<'a, T: 'a, C: 'a> {
+    heap: &'a mut FibonacciHeap<T, C>,
+}
+
+impl<'a, T, C: Compare<T>> Iterator for DrainSorted<'a, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.size, Some(self.heap.size))
+    }
+}
+
+impl<'a, T, C: Compare<T>> ExactSizeIterator for DrainSorted<'a, T, C> {}
+
+impl<'a, T, C> Drop for DrainSorted<'a, T, C> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// Walks every entry of a forest, ring by ring. The links of an entry are read before it's
+/// returned, so the caller may free it right away.
+struct Entries<T> {
+    rings: Vec<Link<T>>,
+    start: Link<T>,
+    current: Link<T>,
+}
+
+impl<T> Entries<T> {
+    fn new(min: &Link<T>) -> Entries<T> {
+        Entries {
+            rings: Vec::new(),
+            start: min.clone(),
+            current: min.clone(),
+        }
+    }
+}
+
+impl<T> Iterator for Entries<T> {
+    type Item = Link<T>;
+
+    fn next(&mut self) -> Option<Link<T>> {
+        if self.current.is_none() {
+            match self.rings.pop() {
+                None => return None,
+                Some(start) => {
+                    self.start = start.clone();
+                    self.current = start;
+                }
+            }
+        }
+
+        let current = self.current.clone();
+
+        let child = current.get_child();
+        if child.is_some() {
+            self.rings.push(child);
+        }
+
+        let next = current.get_next();
+        self.current = if next.are_same(&self.start) {
+            Link::none()
+        } else {
+            next
+        };
+
+        Some(current)
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for FibonacciHeap<T, C> {
+    type Ref<'a> = &'a T where Self: 'a;
+
+    fn push(&mut self, value: T) {
+        FibonacciHeap::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn min<'a>(&'a self) -> Option<&'a T> {
+        FibonacciHeap::min(self)
+    }
+
+    fn size(&self) -> usize {
+        FibonacciHeap::size(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        FibonacciHeap::is_empty(self)
+    }
+}
+
+impl<T, C: Compare<T>> MeldablePriorityQueue<T> for FibonacciHeap<T, C> {
+    fn merge(x: FibonacciHeap<T, C>, y: FibonacciHeap<T, C>) -> FibonacciHeap<T, C> {
+        FibonacciHeap::merge(x, y)
+    }
+
+    fn append(&mut self, other: &mut FibonacciHeap<T, C>) {
+        FibonacciHeap::append(self, other)
+    }
+}
+
+impl<T, C> Drop for FibonacciHeap<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// The heap owns all of its entries and handles can only be used through `&mut self` methods,
+// so sending or sharing the heap is as safe as sending or sharing its values.
+unsafe impl<T: Send, C: Send> Send for FibonacciHeap<T, C> {}
+unsafe impl<T: Sync, C: Sync> Sync for FibonacciHeap<T, C> {}
+
+impl<T> Entry<T> {
     pub fn new(value: T) -> Entry<T> {
         Entry {
             value: value,
 
             degree: 0,
-            // is_marked: false,
+            is_marked: false,
             parent: Link::none(),
             next: Link::none(),
             prev: Link::none(),
@@ -207,7 +755,7 @@ impl<T: PartialOrd> Entry<T> {
     }
 }
 
-impl<T: PartialOrd> Link<T> {
+impl<T> Link<T> {
     #[inline]
     pub fn none() -> Link<T> {
         Link { entry: ptr::null_mut() }
@@ -255,6 +803,42 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
+    #[inline]
+    pub fn dec_degree(&mut self) {
+        if self.is_some() {
+            unsafe {
+                (*self.entry).degree -= 1;
+            }
+        }
+    }
+
+    #[inline]
+    pub fn is_marked(&self) -> bool {
+        if self.is_none() {
+            false
+        } else {
+            unsafe { (*self.entry).is_marked }
+        }
+    }
+
+    #[inline]
+    pub fn set_marked(&mut self, is_marked: bool) {
+        if self.is_some() {
+            unsafe {
+                (*self.entry).is_marked = is_marked;
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: T) {
+        if self.is_some() {
+            unsafe {
+                (*self.entry).value = value;
+            }
+        }
+    }
+
     pub fn into_value(mut self) -> Option<T> {
         if self.is_none() {
             None
@@ -294,6 +878,15 @@ impl<T: PartialOrd> Link<T> {
         }
     }
 
+    #[inline]
+    pub fn get_parent(&self) -> Link<T> {
+        if self.is_none() {
+            Link::<T>::none()
+        } else {
+            unsafe { (*self.entry).parent.clone() }
+        }
+    }
+
     #[inline]
     pub fn get_next(&self) -> Link<T> {
         if self.is_none() {
@@ -349,32 +942,19 @@ impl<T: PartialOrd> Link<T> {
     }
 }
 
-impl<T: PartialOrd> Clone for Link<T> {
+impl<T> Clone for Handle<T> {
     #[inline]
     fn clone(&self) -> Self {
-        Link { entry: self.entry }
+        *self
     }
 }
 
-impl<T: PartialOrd> PartialEq for Link<T> {
-    #[inline]
-    fn eq(&self, other: &Link<T>) -> bool {
-        if self.is_none() || other.is_none() {
-            false
-        } else {
-            unsafe { (*self.entry).value.eq(&(*other.entry).value) }
-        }
-    }
-}
+impl<T> Copy for Handle<T> {}
 
-impl<T: PartialOrd> PartialOrd for Link<T> {
+impl<T> Clone for Link<T> {
     #[inline]
-    fn partial_cmp(&self, other: &Link<T>) -> Option<Ordering> {
-        if self.is_none() || other.is_none() {
-            None
-        } else {
-            unsafe { (*self.entry).value.partial_cmp(&(*other.entry).value) }
-        }
+    fn clone(&self) -> Self {
+        Link { entry: self.entry }
     }
 }
 
@@ -428,6 +1008,276 @@ fn test_order() {
     assert_eq!(8, heap.pop().unwrap());
 }
 
+#[test]
+fn test_decrease_key() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i * 10)).collect();
+
+    // Consolidate, so the decreased entries have parents and cascading cuts happen.
+    assert_eq!(0, heap.pop().unwrap());
+
+    unsafe {
+        heap.decrease_key(&handles[9], 5);
+        heap.decrease_key(&handles[7], 15);
+        heap.decrease_key(&handles[8], 1);
+        heap.decrease_key(&handles[6], 16);
+    }
+
+    assert_eq!(1, *heap.min().unwrap());
+    assert_eq!(vec![1, 5, 10, 15, 16, 20, 30, 40, 50],
+               (0..9).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn test_delete() {
+    let mut heap = FibonacciHeap::new();
+
+    let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+
+    assert_eq!(0, heap.pop().unwrap());
+
+    unsafe {
+        assert_eq!(5, heap.delete(&handles[5]));
+        assert_eq!(1, heap.delete(&handles[1]));
+        assert_eq!(9, heap.delete(&handles[9]));
+    }
+
+    assert_eq!(6, heap.size());
+    assert_eq!(vec![2, 3, 4, 6, 7, 8],
+               (0..6).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_append() {
+    let mut heap: FibonacciHeap<_> = (0..5).map(|i| i * 2).collect();
+    let mut other = FibonacciHeap::new();
+
+    let handle = other.push(9);
+    other.extend((0..4).map(|i| i * 2 + 1));
+    other.pop();
+
+    heap.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(9, heap.size());
+
+    unsafe {
+        heap.decrease_key(&handle, 0);
+    }
+    other.push(1);
+
+    assert_eq!(vec![0, 0, 2, 3, 4, 5, 6, 7, 8], heap.into_sorted_vec());
+    assert_eq!(vec![1], other.into_sorted_vec());
+}
+
+#[test]
+fn test_send_sync() {
+    use std::thread;
+
+    fn check<T: Send + Sync>() {}
+
+    check::<FibonacciHeap<i32>>();
+
+    let heap: FibonacciHeap<_> = (0..10).collect();
+    let heap = thread::spawn(move || heap.into_sorted_vec()).join().unwrap();
+
+    assert_eq!((0..10).collect::<Vec<_>>(), heap);
+}
+
+#[test]
+fn test_max_order() {
+    use super::MaxOrder;
+
+    let mut heap = FibonacciHeap::with_comparator(MaxOrder);
+
+    for &i in &[7, 1, 8, 4, 5, 2, 3, 6] {
+        heap.push(i);
+    }
+
+    assert_eq!(8, *heap.min().unwrap());
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1],
+               (0..8).map(|_| heap.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_comparator() {
+    use super::KeyOrder;
+
+    let mut by_key = FibonacciHeap::with_comparator(KeyOrder::new(|x: &(&str, i32)| x.1));
+    let mut by_closure = FibonacciHeap::with_comparator(|x: &(&str, i32), y: &(&str, i32)| x.0.cmp(y.0));
+
+    for &value in &[("b", 3), ("c", 1), ("a", 2)] {
+        by_key.push(value);
+        by_closure.push(value);
+    }
+
+    assert_eq!(vec!["c", "a", "b"], (0..3).map(|_| by_key.pop().unwrap().0).collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b", "c"],
+               (0..3).map(|_| by_closure.pop().unwrap().0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut heap = FibonacciHeap::new();
+
+        for i in 0..100 {
+            heap.push(DropCounter::new(i, &drops));
+        }
+
+        // Consolidate, so the remaining entries form trees with children.
+        heap.pop();
+        assert_eq!(1, drops.get());
+    }
+
+    assert_eq!(100, drops.get());
+}
+
+#[test]
+fn test_drop_merged() {
+    use std::cell::Cell;
+    use super::DropCounter;
+
+    let drops = Cell::new(0);
+
+    {
+        let mut x = FibonacciHeap::new();
+        let mut y = FibonacciHeap::new();
+
+        for i in 0..10 {
+            x.push(DropCounter::new(i, &drops));
+            y.push(DropCounter::new(i, &drops));
+        }
+
+        x.pop();
+        y.pop();
+
+        let mut merged = FibonacciHeap::merge(x, y);
+        merged.pop();
+        assert_eq!(3, drops.get());
+    }
+
+    assert_eq!(20, drops.get());
+}
+
+#[test]
+fn test_iter() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+    heap.extend(vec![20, 10]);
+
+    let mut values: Vec<_> = heap.iter().cloned().collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+    assert_eq!(11, heap.iter().len());
+
+    let mut values: Vec<_> = heap.into_iter().collect();
+    values.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20], values);
+}
+
+#[test]
+fn test_into_sorted_vec() {
+    let heap: FibonacciHeap<_> = vec![7, 1, 8, 4, 5, 2, 3, 6].into_iter().collect();
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], heap.into_sorted_vec());
+}
+
+#[test]
+fn test_drain_sorted() {
+    let mut heap: FibonacciHeap<_> = (0..10).rev().collect();
+
+    assert_eq!(vec![0, 1, 2], heap.drain_sorted().take(3).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+
+    heap.push(1);
+    assert_eq!(vec![1], heap.drain_sorted().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_clone() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    let handle = heap.push(15);
+    heap.pop();
+
+    let mut copy = heap.clone();
+    unsafe {
+        heap.decrease_key(&handle, 0);
+    }
+
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9], heap.into_sorted_vec());
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 15],
+               (0..10).map(|_| copy.pop().unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_debug() {
+    let heap: FibonacciHeap<_> = Some(1).into_iter().collect();
+
+    assert_eq!("[1]", format!("{:?}", heap));
+}
+
+#[test]
+fn test_check_invariants() {
+    let mut heap = FibonacciHeap::new();
+    heap.check_invariants();
+
+    let handles: Vec<_> = (0..50).map(|i| heap.push((i * 37) % 50 + 100)).collect();
+    heap.check_invariants();
+
+    heap.pop();
+    heap.check_invariants();
+
+    for (i, handle) in handles.iter().enumerate().skip(1).filter(|&(i, _)| i % 3 == 0) {
+        unsafe {
+            heap.decrease_key(handle, i as i32);
+        }
+        heap.check_invariants();
+    }
+
+    for handle in handles.iter().skip(1).take(20) {
+        unsafe {
+            heap.delete(handle);
+        }
+        heap.check_invariants();
+    }
+
+    while heap.pop().is_some() {
+        heap.check_invariants();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_check_invariants_broken() {
+    let mut heap: FibonacciHeap<_> = (0..10).collect();
+    heap.pop();
+
+    heap.size += 1;
+    heap.check_invariants();
+}
+
+#[test]
+fn test_to_dot() {
+    let mut heap = FibonacciHeap::new();
+
+    for &i in &[3, 1, 2] {
+        heap.push(i);
+    }
+
+    heap.pop();
+
+    assert_eq!("digraph {\n    n0 [label=\"2\", peripheries=2];\n    n1 [label=\"3\"];\n    \
+                n0 -> n1;\n}\n",
+               heap.to_dot());
+}
+
 #[bench]
 fn bench_push_pop(b: &mut ::test::Bencher) {
     b.iter(|| {
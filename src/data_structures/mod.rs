@@ -1 +1,2 @@
 pub mod heap;
+pub mod dsu;
@@ -0,0 +1,301 @@
+// Every vertex keeps a list of its neighbours with the indices of the edges leading to them,
+// and edges are kept in one vector, so an undirected edge is stored once for both of its
+// vertices.
+
+use super::{Adjacency, Edge, Neighbors, Edges};
+
+/// Mutable graph with a value of type `N` on every vertex and a weight of type `E` on every
+/// edge.
+#[derive(Clone, Debug)]
+pub struct Graph<N, E> {
+    directed: bool,
+    nodes: Vec<N>,
+    adjacency: Vec<Vec<(usize, usize)>>,
+    edges: Vec<Edge<E>>,
+}
+
+impl<N, E> Graph<N, E> {
+    /// Create an empty graph whose edges go only from their source to their target.
+    pub fn new_directed() -> Graph<N, E> {
+        Graph::new(true)
+    }
+
+    /// Create an empty graph whose edges go both ways.
+    pub fn new_undirected() -> Graph<N, E> {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Graph<N, E> {
+        Graph {
+            directed: directed,
+            nodes: Vec::new(),
+            adjacency: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Add a vertex with `node` to the graph and return its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.push(node);
+        self.adjacency.push(Vec::new());
+
+        self.nodes.len() - 1
+    }
+
+    /// Add an edge from `source` to `target` with `weight` to the graph and return its index.
+    ///
+    /// ##Panics
+    /// If `source` or `target` isn't a vertex of the graph.
+    pub fn add_edge(&mut self, source: usize, target: usize, weight: E) -> usize {
+        assert!(source < self.nodes.len(),
+                "Called add_edge with source = {} >= vertices = {}",
+                source,
+                self.nodes.len());
+        assert!(target < self.nodes.len(),
+                "Called add_edge with target = {} >= vertices = {}",
+                target,
+                self.nodes.len());
+
+        let index = self.edges.len();
+
+        self.adjacency[source].push((target, index));
+        if !self.directed {
+            self.adjacency[target].push((source, index));
+        }
+
+        self.edges.push(Edge {
+            source: source,
+            target: target,
+            weight: weight,
+        });
+
+        index
+    }
+
+    /// Remove the edge with `index` from the graph and return its weight. The last edge takes
+    /// the index of the removed one.
+    ///
+    /// ##Panics
+    /// If `index` isn't an edge of the graph.
+    pub fn remove_edge(&mut self, index: usize) -> E {
+        assert!(index < self.edges.len(),
+                "Called remove_edge with index = {} >= edges = {}",
+                index,
+                self.edges.len());
+
+        let edge = self.edges.swap_remove(index);
+        self.relink(edge.source, edge.target, index, None);
+
+        let last = self.edges.len();
+        if index < last {
+            let (source, target) = (self.edges[index].source, self.edges[index].target);
+            self.relink(source, target, last, Some(index));
+        }
+
+        edge.weight
+    }
+
+    /// Remove the neighbour entries of the edge from `source` to `target` stored with `index`,
+    /// or give them `new_index`.
+    fn relink(&mut self, source: usize, target: usize, index: usize, new_index: Option<usize>) {
+        relink(&mut self.adjacency[source], index, new_index);
+
+        if !self.directed && target != source {
+            relink(&mut self.adjacency[target], index, new_index);
+        }
+    }
+
+    /// Return the value of `vertex`.
+    pub fn node(&self, vertex: usize) -> &N {
+        &self.nodes[vertex]
+    }
+
+    /// Return the mutable value of `vertex`.
+    pub fn node_mut(&mut self, vertex: usize) -> &mut N {
+        &mut self.nodes[vertex]
+    }
+
+    /// Return the edge with `index`.
+    pub fn edge(&self, index: usize) -> &Edge<E> {
+        &self.edges[index]
+    }
+
+    /// Return the mutable weight of the edge with `index`.
+    pub fn weight_mut(&mut self, index: usize) -> &mut E {
+        &mut self.edges[index].weight
+    }
+
+    /// Return the number of edges leaving `vertex`. A loop counts twice in undirected graphs.
+    pub fn degree(&self, vertex: usize) -> usize {
+        self.adjacency[vertex].len()
+    }
+
+    pub fn neighbors(&self, vertex: usize) -> Neighbors<'_, E> {
+        Neighbors::new(&self.adjacency[vertex], &self.edges)
+    }
+
+    pub fn edges(&self) -> Edges<'_, E> {
+        Edges::new(&self.edges)
+    }
+
+    /// Split the graph into its vertex values, neighbour entries and edges.
+    pub(super) fn into_parts(self) -> (bool, Vec<Vec<(usize, usize)>>, Vec<Edge<E>>) {
+        (self.directed, self.adjacency, self.edges)
+    }
+}
+
+impl<N: Default, E> Graph<N, E> {
+    /// Create a directed graph with `vertices` default vertices and `edges` given as
+    /// `(source, target, weight)`.
+    ///
+    /// ##Panics
+    /// If an edge has a vertex which isn't smaller than `vertices`.
+    pub fn directed_from_edges<I>(vertices: usize, edges: I) -> Graph<N, E>
+        where I: IntoIterator<Item = (usize, usize, E)>
+    {
+        Graph::from_edges(true, vertices, edges)
+    }
+
+    /// Create an undirected graph with `vertices` default vertices and `edges` given as
+    /// `(source, target, weight)`.
+    ///
+    /// ##Panics
+    /// If an edge has a vertex which isn't smaller than `vertices`.
+    pub fn undirected_from_edges<I>(vertices: usize, edges: I) -> Graph<N, E>
+        where I: IntoIterator<Item = (usize, usize, E)>
+    {
+        Graph::from_edges(false, vertices, edges)
+    }
+
+    fn from_edges<I>(directed: bool, vertices: usize, edges: I) -> Graph<N, E>
+        where I: IntoIterator<Item = (usize, usize, E)>
+    {
+        let mut graph = Graph::new(directed);
+
+        for _ in 0..vertices {
+            graph.add_node(N::default());
+        }
+
+        for (source, target, weight) in edges {
+            graph.add_edge(source, target, weight);
+        }
+
+        graph
+    }
+}
+
+fn relink(adjacent: &mut Vec<(usize, usize)>, index: usize, new_index: Option<usize>) {
+    match new_index {
+        None => adjacent.retain(|&(_, edge)| edge != index),
+        Some(new_index) => {
+            for entry in adjacent.iter_mut().filter(|entry| entry.1 == index) {
+                entry.1 = new_index;
+            }
+        }
+    }
+}
+
+impl<N, E> Adjacency for Graph<N, E> {
+    type Weight = E;
+
+    fn is_directed(&self) -> bool {
+        Graph::is_directed(self)
+    }
+
+    fn vertex_count(&self) -> usize {
+        Graph::vertex_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        Graph::edge_count(self)
+    }
+
+    fn neighbors(&self, vertex: usize) -> Neighbors<'_, E> {
+        Graph::neighbors(self, vertex)
+    }
+
+    fn edges(&self) -> Edges<'_, E> {
+        Graph::edges(self)
+    }
+}
+
+#[test]
+fn test_add() {
+    let mut graph = Graph::new_directed();
+
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+
+    assert_eq!(0, graph.add_edge(a, b, 1.5));
+    assert_eq!(1, graph.add_edge(b, b, 2.0));
+
+    assert_eq!(2, graph.vertex_count());
+    assert_eq!(2, graph.edge_count());
+    assert_eq!("b", *graph.node(b));
+    assert_eq!(Edge { source: b, target: b, weight: 2.0 }, *graph.edge(1));
+    assert_eq!(1, graph.degree(a));
+    assert_eq!(vec![(b, &1.5)], graph.neighbors(a).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_undirected() {
+    let mut graph: Graph<(), _> = Graph::undirected_from_edges(3, vec![(0, 1, 'a'), (1, 2, 'b')]);
+
+    graph.add_edge(2, 2, 'c');
+
+    assert_eq!(vec![(0, &'a'), (2, &'b')], graph.neighbors(1).collect::<Vec<_>>());
+    assert_eq!(vec![(1, &'b'), (2, &'c'), (2, &'c')], graph.neighbors(2).collect::<Vec<_>>());
+    assert_eq!(3, graph.degree(2));
+}
+
+#[test]
+fn test_node_mut() {
+    let mut graph: Graph<i32, ()> = Graph::directed_from_edges(2, vec![(0, 1, ())]);
+
+    *graph.node_mut(1) += 5;
+    *graph.weight_mut(0) = ();
+
+    assert_eq!(5, *graph.node(1));
+}
+
+#[test]
+fn test_remove_edge() {
+    let edges = vec![(0, 1, 'a'), (1, 2, 'b'), (2, 2, 'c'), (2, 0, 'd')];
+
+    let mut graph: Graph<(), _> = Graph::undirected_from_edges(3, edges);
+
+    assert_eq!('a', graph.remove_edge(0));
+
+    // The last edge takes index 0.
+    assert_eq!(Edge { source: 2, target: 0, weight: 'd' }, *graph.edge(0));
+    assert_eq!(vec![(2, &'d')], graph.neighbors(0).collect::<Vec<_>>());
+    assert_eq!(vec![(2, &'b')], graph.neighbors(1).collect::<Vec<_>>());
+
+    assert_eq!('c', graph.remove_edge(2));
+    assert_eq!(vec![(1, &'b'), (0, &'d')], graph.neighbors(2).collect::<Vec<_>>());
+
+    assert_eq!('d', graph.remove_edge(0));
+    assert_eq!(1, graph.edge_count());
+    assert_eq!(0, graph.degree(0));
+    assert_eq!(vec![(1, 2, &'b')], graph.edges().collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn test_add_edge_missing_vertex() {
+    let mut graph: Graph<(), ()> = Graph::directed_from_edges(2, vec![]);
+
+    graph.add_edge(0, 2, ());
+}
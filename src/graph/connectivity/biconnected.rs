@@ -0,0 +1,284 @@
+// Based on "Depth-First Search and Linear Graph Algorithms" by Robert Tarjan.
+//
+// Vertices are numbered in the order a depth-first search discovers them, and the low link of a
+// vertex is the smallest number reachable from its subtree by one edge which isn't the tree edge
+// to its parent. When a child finishes with a low link of at least the number of its parent,
+// nothing in the subtree of the child reaches above the parent, so the parent separates the
+// subtree, and the vertices discovered since the child with the parent are a biconnected
+// component. If the low link is even larger, the tree edge is a bridge.
+//
+// Only the first edge back to the parent is skipped, so a parallel edge counts as a way back
+// and the edges it's parallel to aren't bridges. The search keeps a stack of neighbour
+// iterators instead of recursing.
+
+use graph::{Adjacency, Neighbors, check_undirected};
+
+/// Bridges, articulation points and biconnected components of an undirected graph.
+struct Decomposition {
+    bridges: Vec<(usize, usize)>,
+    articulation_points: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+/// Return the bridges of the undirected `graph`, the edges whose removal disconnects their
+/// endpoints, as pairs of endpoints with the smaller first, in increasing order.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn bridges<G: Adjacency>(graph: &G) -> Vec<(usize, usize)> {
+    check_undirected(graph, "bridges");
+
+    decompose(graph).bridges
+}
+
+/// Return the articulation points of the undirected `graph`, the vertices whose removal
+/// disconnects other vertices of their connected component, in increasing order.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn articulation_points<G: Adjacency>(graph: &G) -> Vec<usize> {
+    check_undirected(graph, "articulation_points");
+
+    decompose(graph).articulation_points
+}
+
+/// Return the vertices of every biconnected component of the undirected `graph`, the maximal
+/// subgraphs which removing one vertex doesn't disconnect, in increasing order. Components are
+/// ordered by their vertices, and every vertex without other neighbours is a component alone.
+///
+/// Articulation points are in all components they separate, and all other vertices are in one
+/// component.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn biconnected_components<G: Adjacency>(graph: &G) -> Vec<Vec<usize>> {
+    check_undirected(graph, "biconnected_components");
+
+    decompose(graph).components
+}
+
+fn decompose<G: Adjacency>(graph: &G) -> Decomposition {
+    let vertices = graph.vertex_count();
+
+    let mut indices = vec![0; vertices];
+    let mut low_links = vec![0; vertices];
+    let mut is_discovered = vec![false; vertices];
+    let mut parents: Vec<Option<usize>> = vec![None; vertices];
+    let mut is_parent_skipped = vec![false; vertices];
+    let mut is_articulation_point = vec![false; vertices];
+    let mut index = 0;
+
+    let mut decomposition = Decomposition {
+        bridges: Vec::new(),
+        articulation_points: Vec::new(),
+        components: Vec::new(),
+    };
+
+    let mut component_stack = Vec::new();
+    let mut stack: Vec<(usize, Neighbors<G::Weight>)> = Vec::new();
+
+    for root in 0..vertices {
+        if is_discovered[root] {
+            continue;
+        }
+
+        is_discovered[root] = true;
+        indices[root] = index;
+        low_links[root] = index;
+        index += 1;
+        stack.push((root, graph.neighbors(root)));
+
+        let mut root_children = 0;
+
+        while let Some(&mut (vertex, ref mut neighbors)) = stack.last_mut() {
+            let neighbor = match neighbors.next() {
+                None => {
+                    stack.pop();
+
+                    if let Some(parent) = parents[vertex] {
+                        low_links[parent] = low_links[parent].min(low_links[vertex]);
+
+                        if low_links[vertex] > indices[parent] {
+                            decomposition.bridges.push((parent.min(vertex), parent.max(vertex)));
+                        }
+
+                        if low_links[vertex] >= indices[parent] {
+                            if parent != root {
+                                is_articulation_point[parent] = true;
+                            }
+
+                            let mut component = vec![parent];
+                            loop {
+                                let member = component_stack.pop().unwrap();
+                                component.push(member);
+
+                                if member == vertex {
+                                    break;
+                                }
+                            }
+
+                            component.sort();
+                            decomposition.components.push(component);
+                        }
+                    }
+
+                    continue;
+                }
+                Some((neighbor, _)) => neighbor,
+            };
+
+            if !is_discovered[neighbor] {
+                if vertex == root {
+                    root_children += 1;
+                }
+
+                is_discovered[neighbor] = true;
+                indices[neighbor] = index;
+                low_links[neighbor] = index;
+                index += 1;
+                parents[neighbor] = Some(vertex);
+                component_stack.push(neighbor);
+                stack.push((neighbor, graph.neighbors(neighbor)));
+            } else if parents[vertex] == Some(neighbor) && !is_parent_skipped[vertex] {
+                is_parent_skipped[vertex] = true;
+            } else {
+                low_links[vertex] = low_links[vertex].min(indices[neighbor]);
+            }
+        }
+
+        if root_children > 1 {
+            is_articulation_point[root] = true;
+        } else if root_children == 0 {
+            decomposition.components.push(vec![root]);
+        }
+    }
+
+    decomposition.bridges.sort();
+    decomposition.articulation_points =
+        (0..vertices).filter(|&vertex| is_articulation_point[vertex]).collect();
+    decomposition.components.sort();
+
+    decomposition
+}
+
+/// Return the number of connected components of `graph` without `removed_vertex` and the edge
+/// with index `removed_edge`.
+#[cfg(test)]
+fn count_components<G: Adjacency>(graph: &G,
+                                  removed_vertex: Option<usize>,
+                                  removed_edge: Option<usize>)
+                                  -> usize {
+    use data_structures::dsu::DisjointSet;
+
+    let mut sets = DisjointSet::new(graph.vertex_count());
+
+    for (index, (source, target, _)) in graph.edges().enumerate() {
+        let is_removed = Some(index) == removed_edge || Some(source) == removed_vertex ||
+                         Some(target) == removed_vertex;
+
+        if !is_removed {
+            sets.union(source, target);
+        }
+    }
+
+    sets.set_count() - if removed_vertex.is_some() { 1 } else { 0 }
+}
+
+#[cfg(test)]
+fn test_graph() -> ::graph::Graph<(), ()> {
+    // Triangle 0-1-2 hanging from 3 by the bridge 2-3, square 3-4-5-6, bridge 6-7, parallel
+    // edges 7-8, a loop on 8 and the isolated vertex 9.
+    ::graph::Graph::undirected_from_edges(10,
+                                          vec![(0, 1, ()),
+                                               (1, 2, ()),
+                                               (2, 0, ()),
+                                               (2, 3, ()),
+                                               (3, 4, ()),
+                                               (4, 5, ()),
+                                               (5, 6, ()),
+                                               (6, 3, ()),
+                                               (6, 7, ()),
+                                               (7, 8, ()),
+                                               (8, 7, ()),
+                                               (8, 8, ())])
+}
+
+#[test]
+fn test_bridges() {
+    assert_eq!(vec![(2, 3), (6, 7)], bridges(&test_graph()));
+}
+
+#[test]
+fn test_articulation_points() {
+    assert_eq!(vec![2, 3, 6, 7], articulation_points(&test_graph()));
+}
+
+#[test]
+fn test_biconnected_components() {
+    assert_eq!(vec![vec![0, 1, 2], vec![2, 3], vec![3, 4, 5, 6], vec![6, 7], vec![7, 8], vec![9]],
+               biconnected_components(&test_graph()));
+}
+
+#[test]
+fn test_random() {
+    use graph::{CsrGraph, random_edges};
+
+    for seed in 1..31 {
+        let edges = random_edges(25, 20 + seed as usize / 2, 0, 1, seed);
+        let graph = CsrGraph::undirected_from_edges(25, edges.iter().cloned());
+        let components = count_components(&graph, None, None);
+
+        let mut expected_bridges: Vec<_> = edges.iter()
+            .enumerate()
+            .filter(|&(index, _)| count_components(&graph, None, Some(index)) > components)
+            .map(|(_, &(source, target, _))| (source.min(target), source.max(target)))
+            .collect();
+        expected_bridges.sort();
+
+        let expected_points: Vec<_> = (0..25)
+            .filter(|&vertex| count_components(&graph, Some(vertex), None) > components)
+            .collect();
+
+        assert_eq!(expected_bridges, bridges(&graph));
+        assert_eq!(expected_points, articulation_points(&graph));
+
+        // The endpoints of every edge which isn't a loop are in exactly one component together.
+        let blocks = biconnected_components(&graph);
+        for &(source, target, _) in &edges {
+            if source != target {
+                let together = blocks.iter()
+                    .filter(|block| block.contains(&source) && block.contains(&target))
+                    .count();
+
+                assert_eq!(1, together);
+            }
+        }
+
+        for vertex in 0..25 {
+            let count = blocks.iter().filter(|block| block.contains(&vertex)).count();
+
+            assert_eq!(expected_points.contains(&vertex), count > 1);
+        }
+    }
+}
+
+#[test]
+fn test_deep() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::undirected_from_edges(1000000, (1..1000000).map(|v| (v - 1, v, ())));
+
+    assert_eq!(999999, bridges(&graph).len());
+    assert_eq!(999998, articulation_points(&graph).len());
+    assert_eq!(999999, biconnected_components(&graph).len());
+}
+
+#[test]
+#[should_panic]
+fn test_directed() {
+    use graph::Graph;
+
+    let graph: Graph<(), ()> = Graph::directed_from_edges(2, vec![(0, 1, ())]);
+
+    bridges(&graph);
+}
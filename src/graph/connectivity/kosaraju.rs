@@ -0,0 +1,80 @@
+// Kosaraju's algorithm, after "A Strong-Connectivity Algorithm and Its Applications in Data Flow
+// Analysis" by Micha Sharir.
+//
+// A depth-first search orders the vertices by their finishing times. The vertex finished last is
+// in a source component, so a search from it over the reversed edges reaches exactly its
+// component. Repeating this from the unassigned vertex finished last finds the components in a
+// topological order.
+
+use graph::{Adjacency, Neighbors};
+use super::Components;
+
+/// Return the strongly connected components of `graph` using Kosaraju's algorithm.
+pub fn kosaraju<G: Adjacency>(graph: &G) -> Components {
+    let vertices = graph.vertex_count();
+
+    let mut is_discovered = vec![false; vertices];
+    let mut order = Vec::with_capacity(vertices);
+    let mut stack: Vec<(usize, Neighbors<G::Weight>)> = Vec::new();
+
+    for root in 0..vertices {
+        if is_discovered[root] {
+            continue;
+        }
+
+        is_discovered[root] = true;
+        stack.push((root, graph.neighbors(root)));
+
+        while let Some(&mut (vertex, ref mut neighbors)) = stack.last_mut() {
+            let neighbor = match neighbors.next() {
+                None => {
+                    order.push(vertex);
+                    stack.pop();
+                    continue;
+                }
+                Some((neighbor, _)) => neighbor,
+            };
+
+            if !is_discovered[neighbor] {
+                is_discovered[neighbor] = true;
+                stack.push((neighbor, graph.neighbors(neighbor)));
+            }
+        }
+    }
+
+    let mut reversed = vec![Vec::new(); vertices];
+    for vertex in 0..vertices {
+        for (neighbor, _) in graph.neighbors(vertex) {
+            reversed[neighbor].push(vertex);
+        }
+    }
+
+    let mut components = vec![None; vertices];
+    let mut count = 0;
+    let mut pending = Vec::new();
+
+    for &root in order.iter().rev() {
+        if components[root].is_some() {
+            continue;
+        }
+
+        components[root] = Some(count);
+        pending.push(root);
+
+        while let Some(vertex) = pending.pop() {
+            for &neighbor in &reversed[vertex] {
+                if components[neighbor].is_none() {
+                    components[neighbor] = Some(count);
+                    pending.push(neighbor);
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    Components {
+        components: components.into_iter().map(Option::unwrap).collect(),
+        count: count,
+    }
+}
@@ -0,0 +1,199 @@
+pub use self::tarjan::tarjan;
+pub use self::kosaraju::kosaraju;
+pub use self::biconnected::{bridges, articulation_points, biconnected_components};
+
+use graph::{Adjacency, Graph};
+
+mod tarjan;
+mod kosaraju;
+mod biconnected;
+
+/// Strongly connected components of a graph, numbered in a topological order of the
+/// condensation: every edge between two components goes to the one with the larger number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Components {
+    components: Vec<usize>,
+    count: usize,
+}
+
+impl Components {
+    /// Return the number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Return the number of the component of `vertex`.
+    pub fn component(&self, vertex: usize) -> usize {
+        self.components[vertex]
+    }
+
+    /// Return the vertices of every component in increasing order, indexed by the numbers of
+    /// the components.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = vec![Vec::new(); self.count];
+        for (vertex, &component) in self.components.iter().enumerate() {
+            components[component].push(vertex);
+        }
+
+        components
+    }
+}
+
+/// Return the condensation of `graph` by its strongly connected `components`: the directed
+/// acyclic graph with a vertex for every component, holding the vertices of the component, and
+/// an edge between two components if an edge of `graph` goes between them.
+///
+/// Edges are added by increasing numbers of their source components, without duplicates.
+///
+/// ##Panics
+/// If `components` aren't of a graph with the vertices of `graph`.
+pub fn condensation<G: Adjacency>(graph: &G, components: &Components) -> Graph<Vec<usize>, ()> {
+    assert!(components.components.len() == graph.vertex_count(),
+            "Called condensation with components of {} vertices != vertices = {}",
+            components.components.len(),
+            graph.vertex_count());
+
+    let sets = components.components();
+
+    let mut condensation = Graph::new_directed();
+    for vertices in &sets {
+        condensation.add_node(vertices.clone());
+    }
+
+    // The last component with an edge added to every component.
+    let mut last_sources = vec![None; components.count];
+
+    for (component, vertices) in sets.iter().enumerate() {
+        for &vertex in vertices {
+            for (neighbor, _) in graph.neighbors(vertex) {
+                let target = components.component(neighbor);
+
+                if target != component && last_sources[target] != Some(component) {
+                    last_sources[target] = Some(component);
+                    condensation.add_edge(component, target, ());
+                }
+            }
+        }
+    }
+
+    condensation
+}
+
+/// Check that `components` are the strongly connected components of `graph` numbered in a
+/// topological order, by comparing reachability from every vertex.
+#[cfg(test)]
+fn check_components<G: Adjacency>(graph: &G, components: &Components) {
+    use graph::traversal::Bfs;
+
+    let vertices = graph.vertex_count();
+    let reachable: Vec<Vec<bool>> = (0..vertices)
+        .map(|source| {
+            let mut reachable = vec![false; vertices];
+            for vertex in Bfs::new(graph, source) {
+                reachable[vertex] = true;
+            }
+
+            reachable
+        })
+        .collect();
+
+    for x in 0..vertices {
+        for y in 0..vertices {
+            let same = reachable[x][y] && reachable[y][x];
+
+            assert_eq!(same, components.component(x) == components.component(y));
+        }
+
+        for (neighbor, _) in graph.neighbors(x) {
+            assert!(components.component(x) <= components.component(neighbor));
+        }
+    }
+
+    assert!(components.components().iter().all(|component| !component.is_empty()));
+}
+
+#[cfg(test)]
+fn test_graph() -> Graph<(), ()> {
+    Graph::directed_from_edges(8,
+                               vec![(0, 1, ()),
+                                    (1, 2, ()),
+                                    (2, 0, ()),
+                                    (2, 3, ()),
+                                    (3, 4, ()),
+                                    (4, 5, ()),
+                                    (5, 3, ()),
+                                    (6, 5, ()),
+                                    (6, 7, ()),
+                                    (7, 6, ()),
+                                    (1, 4, ())])
+}
+
+#[test]
+fn test_components() {
+    let graph = test_graph();
+
+    for components in vec![tarjan(&graph), kosaraju(&graph)] {
+        check_components(&graph, &components);
+
+        let mut sets = components.components();
+        sets.sort();
+        assert_eq!(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]], sets);
+    }
+}
+
+#[test]
+fn test_random() {
+    use graph::{CsrGraph, random_edges};
+
+    for seed in 1..31 {
+        let edges = random_edges(30, 20 + seed as usize, 0, 1, seed);
+        let graph = CsrGraph::directed_from_edges(30, edges);
+
+        let tarjan = tarjan(&graph);
+        let kosaraju = kosaraju(&graph);
+
+        check_components(&graph, &tarjan);
+        check_components(&graph, &kosaraju);
+        assert_eq!(tarjan.count(), kosaraju.count());
+    }
+}
+
+#[test]
+fn test_condensation() {
+    let graph = test_graph();
+    let components = kosaraju(&graph);
+    let condensation = condensation(&graph, &components);
+
+    let (first, second, third) = (components.component(0),
+                                  components.component(3),
+                                  components.component(6));
+
+    assert_eq!(3, condensation.vertex_count());
+    assert_eq!(&vec![0, 1, 2], condensation.node(first));
+    assert_eq!(&vec![3, 4, 5], condensation.node(second));
+    assert_eq!(&vec![6, 7], condensation.node(third));
+
+    // The edges 2 -> 3 and 1 -> 4 become one edge.
+    let mut edges: Vec<_> = condensation.edges().map(|(s, t, _)| (s, t)).collect();
+    edges.sort();
+    let mut expected = vec![(first, second), (third, second)];
+    expected.sort();
+    assert_eq!(expected, edges);
+    assert!(edges.iter().all(|&(source, target)| source < target));
+}
+
+#[test]
+fn test_deep() {
+    use graph::CsrGraph;
+
+    // A cycle through many vertices, and a path of many components.
+    let cycle = CsrGraph::directed_from_edges(1000000,
+                                              (0..1000000).map(|v| (v, (v + 1) % 1000000, ())));
+    let path = CsrGraph::directed_from_edges(1000000, (1..1000000).map(|v| (v - 1, v, ())));
+
+    assert_eq!(1, tarjan(&cycle).count());
+    assert_eq!(1, kosaraju(&cycle).count());
+
+    assert_eq!(1000000, tarjan(&path).count());
+    assert_eq!(999999, kosaraju(&path).component(999999));
+}
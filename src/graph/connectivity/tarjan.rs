@@ -0,0 +1,92 @@
+// Based on "Depth-First Search and Linear Graph Algorithms" by Robert Tarjan.
+//
+// Vertices are numbered in the order the depth-first search discovers them, and the low link of
+// a vertex is the smallest number reachable from its subtree by one edge to a vertex still on
+// the component stack. A vertex whose low link is its own number is the root of a component,
+// which is popped from the component stack when the vertex finishes.
+//
+// Components are found sinks first, so their numbers are reversed at the end. The search keeps
+// a stack of neighbour iterators instead of recursing.
+
+use graph::{Adjacency, Neighbors};
+use super::Components;
+
+/// Return the strongly connected components of `graph` using Tarjan's algorithm.
+pub fn tarjan<G: Adjacency>(graph: &G) -> Components {
+    let vertices = graph.vertex_count();
+
+    let mut indices = vec![0; vertices];
+    let mut low_links = vec![0; vertices];
+    let mut is_discovered = vec![false; vertices];
+    let mut is_on_stack = vec![false; vertices];
+    let mut components = vec![0; vertices];
+    let mut count = 0;
+    let mut index = 0;
+
+    let mut component_stack = Vec::new();
+    let mut stack: Vec<(usize, Neighbors<G::Weight>)> = Vec::new();
+
+    for root in 0..vertices {
+        if is_discovered[root] {
+            continue;
+        }
+
+        is_discovered[root] = true;
+        indices[root] = index;
+        low_links[root] = index;
+        index += 1;
+        is_on_stack[root] = true;
+        component_stack.push(root);
+        stack.push((root, graph.neighbors(root)));
+
+        while let Some(&mut (vertex, ref mut neighbors)) = stack.last_mut() {
+            let neighbor = match neighbors.next() {
+                None => {
+                    stack.pop();
+
+                    if let Some(&(parent, _)) = stack.last() {
+                        low_links[parent] = low_links[parent].min(low_links[vertex]);
+                    }
+
+                    if low_links[vertex] == indices[vertex] {
+                        loop {
+                            let member = component_stack.pop().unwrap();
+                            is_on_stack[member] = false;
+                            components[member] = count;
+
+                            if member == vertex {
+                                break;
+                            }
+                        }
+
+                        count += 1;
+                    }
+
+                    continue;
+                }
+                Some((neighbor, _)) => neighbor,
+            };
+
+            if !is_discovered[neighbor] {
+                is_discovered[neighbor] = true;
+                indices[neighbor] = index;
+                low_links[neighbor] = index;
+                index += 1;
+                is_on_stack[neighbor] = true;
+                component_stack.push(neighbor);
+                stack.push((neighbor, graph.neighbors(neighbor)));
+            } else if is_on_stack[neighbor] {
+                low_links[vertex] = low_links[vertex].min(indices[neighbor]);
+            }
+        }
+    }
+
+    for component in &mut components {
+        *component = count - 1 - *component;
+    }
+
+    Components {
+        components: components,
+        count: count,
+    }
+}
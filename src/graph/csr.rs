@@ -0,0 +1,168 @@
+// Compressed sparse row layout: the neighbour entries of all vertices are kept in one vector,
+// ordered by vertex, and the entries of vertex `v` are `adjacent[offsets[v]..offsets[v + 1]]`.
+// Traversals read memory in order instead of following a pointer per vertex.
+
+use super::{Adjacency, Edge, Neighbors, Edges, Graph};
+
+/// Immutable graph with a weight of type `E` on every edge, laid out for fast traversal.
+#[derive(Clone, Debug)]
+pub struct CsrGraph<E> {
+    directed: bool,
+    offsets: Vec<usize>,
+    adjacent: Vec<(usize, usize)>,
+    edges: Vec<Edge<E>>,
+}
+
+impl<E> CsrGraph<E> {
+    /// Create a directed graph with `vertices` vertices and `edges` given as
+    /// `(source, target, weight)`.
+    ///
+    /// ##Panics
+    /// If an edge has a vertex which isn't smaller than `vertices`.
+    pub fn directed_from_edges<I>(vertices: usize, edges: I) -> CsrGraph<E>
+        where I: IntoIterator<Item = (usize, usize, E)>
+    {
+        Graph::<(), E>::directed_from_edges(vertices, edges).into()
+    }
+
+    /// Create an undirected graph with `vertices` vertices and `edges` given as
+    /// `(source, target, weight)`.
+    ///
+    /// ##Panics
+    /// If an edge has a vertex which isn't smaller than `vertices`.
+    pub fn undirected_from_edges<I>(vertices: usize, edges: I) -> CsrGraph<E>
+        where I: IntoIterator<Item = (usize, usize, E)>
+    {
+        Graph::<(), E>::undirected_from_edges(vertices, edges).into()
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return the edge with `index`.
+    pub fn edge(&self, index: usize) -> &Edge<E> {
+        &self.edges[index]
+    }
+
+    /// Return the number of edges leaving `vertex`. A loop counts twice in undirected graphs.
+    pub fn degree(&self, vertex: usize) -> usize {
+        self.offsets[vertex + 1] - self.offsets[vertex]
+    }
+
+    pub fn neighbors(&self, vertex: usize) -> Neighbors<'_, E> {
+        let adjacent = &self.adjacent[self.offsets[vertex]..self.offsets[vertex + 1]];
+
+        Neighbors::new(adjacent, &self.edges)
+    }
+
+    pub fn edges(&self) -> Edges<'_, E> {
+        Edges::new(&self.edges)
+    }
+}
+
+impl<N, E> From<Graph<N, E>> for CsrGraph<E> {
+    /// Lay out `graph` without its vertex values. Vertices and edges keep their indices.
+    fn from(graph: Graph<N, E>) -> CsrGraph<E> {
+        let (directed, adjacency, edges) = graph.into_parts();
+
+        let mut offsets = Vec::with_capacity(adjacency.len() + 1);
+        let mut adjacent = Vec::with_capacity(adjacency.iter().map(|list| list.len()).sum());
+
+        offsets.push(0);
+        for list in adjacency {
+            adjacent.extend(list);
+            offsets.push(adjacent.len());
+        }
+
+        CsrGraph {
+            directed: directed,
+            offsets: offsets,
+            adjacent: adjacent,
+            edges: edges,
+        }
+    }
+}
+
+impl<E> Adjacency for CsrGraph<E> {
+    type Weight = E;
+
+    fn is_directed(&self) -> bool {
+        CsrGraph::is_directed(self)
+    }
+
+    fn vertex_count(&self) -> usize {
+        CsrGraph::vertex_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        CsrGraph::edge_count(self)
+    }
+
+    fn neighbors(&self, vertex: usize) -> Neighbors<'_, E> {
+        CsrGraph::neighbors(self, vertex)
+    }
+
+    fn edges(&self) -> Edges<'_, E> {
+        CsrGraph::edges(self)
+    }
+}
+
+#[test]
+fn test_from_edges() {
+    let graph = CsrGraph::directed_from_edges(4, vec![(2, 0, 'a'), (0, 1, 'b'), (2, 3, 'c')]);
+
+    assert_eq!(4, graph.vertex_count());
+    assert_eq!(3, graph.edge_count());
+    assert_eq!(vec![(0, &'a'), (3, &'c')], graph.neighbors(2).collect::<Vec<_>>());
+    assert_eq!(0, graph.degree(3));
+    assert_eq!(Edge { source: 0, target: 1, weight: 'b' }, *graph.edge(1));
+}
+
+#[test]
+fn test_undirected() {
+    let graph = CsrGraph::undirected_from_edges(3, vec![(0, 1, 1), (1, 2, 2), (1, 1, 3)]);
+
+    assert_eq!(vec![(0, &1), (2, &2), (1, &3), (1, &3)], graph.neighbors(1).collect::<Vec<_>>());
+    assert_eq!(4, graph.degree(1));
+}
+
+#[test]
+fn test_from_graph() {
+    let mut graph = Graph::new_directed();
+
+    for name in "abcd".chars() {
+        graph.add_node(name);
+    }
+
+    graph.add_edge(3, 0, 1.0);
+    graph.add_edge(0, 3, 2.0);
+    graph.add_edge(1, 2, 3.0);
+    graph.remove_edge(0);
+
+    let csr = CsrGraph::from(graph.clone());
+
+    assert_eq!(graph.vertex_count(), csr.vertex_count());
+    for vertex in 0..4 {
+        assert_eq!(graph.neighbors(vertex).collect::<Vec<_>>(),
+                   csr.neighbors(vertex).collect::<Vec<_>>());
+    }
+
+    assert_eq!(graph.edges().collect::<Vec<_>>(), csr.edges().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_empty() {
+    let graph: CsrGraph<()> = CsrGraph::directed_from_edges(0, vec![]);
+
+    assert_eq!(0, graph.vertex_count());
+    assert_eq!(0, graph.edges().count());
+}
@@ -0,0 +1,112 @@
+// Based on "Algorithm for Solution of a Problem of Maximum Flow in Networks with Power
+// Estimation" by E. A. Dinic.
+//
+// Every phase finds the distances from the source by arcs with residual capacity, and saturates
+// the level graph of the arcs going one level further with augmenting paths, until the sink is
+// out of reach. A phase makes the distance of the sink grow, so there are O(V) phases.
+//
+// Paths are followed from the source with a stack of arcs instead of recursion. Every vertex
+// keeps its current arc, and arcs behind it are saturated or lead to dead ends, so a phase takes
+// O(VE).
+
+use graph::{Adjacency, Weight};
+use super::{MaximumFlow, Network};
+
+/// Return a maximum flow from `source` to `sink` of `graph`, whose weights are the capacities
+/// of the edges, using Dinic's algorithm.
+///
+/// ##Panics
+/// If `source` or `sink` isn't a vertex of `graph`, if they're the same vertex, or if an edge
+/// has a negative capacity.
+pub fn dinic<G>(graph: &G, source: usize, sink: usize) -> MaximumFlow<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    let mut network = Network::new(graph, source, sink, "dinic");
+    let zero = G::Weight::zero();
+
+    loop {
+        let (mut levels, _) = network.search();
+
+        if levels[sink].is_none() {
+            break;
+        }
+
+        let mut current_arcs = vec![0; network.vertex_count()];
+        let mut path = Vec::new();
+        let mut vertex = source;
+
+        loop {
+            if vertex == sink {
+                let mut amount = network.residuals[path[0]];
+                for &arc in &path {
+                    if network.residuals[arc] < amount {
+                        amount = network.residuals[arc];
+                    }
+                }
+
+                for &arc in &path {
+                    network.push(arc, amount);
+                }
+
+                // Continue from the tail of the first saturated arc.
+                let saturated = path.iter()
+                    .position(|&arc| !(zero < network.residuals[arc]))
+                    .unwrap();
+                vertex = network.heads[path[saturated] ^ 1];
+                path.truncate(saturated);
+
+                continue;
+            }
+
+            let next = {
+                let arcs = &network.adjacency[vertex];
+                let current = &mut current_arcs[vertex];
+
+                while *current < arcs.len() {
+                    let arc = arcs[*current];
+                    let head = network.heads[arc];
+
+                    if zero < network.residuals[arc] &&
+                       levels[head] == levels[vertex].map(|level| level + 1) {
+                        break;
+                    }
+
+                    *current += 1;
+                }
+
+                arcs.get(*current).cloned()
+            };
+
+            match next {
+                Some(arc) => {
+                    path.push(arc);
+                    vertex = network.heads[arc];
+                }
+                None => {
+                    if vertex == source {
+                        break;
+                    }
+
+                    // Dead end, which no path of this phase goes through anymore.
+                    levels[vertex] = None;
+
+                    let arc = path.pop().unwrap();
+                    vertex = network.heads[arc ^ 1];
+                    current_arcs[vertex] += 1;
+                }
+            }
+        }
+    }
+
+    network.into_flow()
+}
+
+#[bench]
+fn bench_dinic(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::directed_from_edges(1000, random_edges(1000, 10000, 1, 1000, 1));
+
+    b.iter(|| dinic(&graph, 0, 999))
+}
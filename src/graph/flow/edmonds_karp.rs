@@ -0,0 +1,60 @@
+// Based on "Theoretical Improvements in Algorithmic Efficiency for Network Flow Problems" by Jack
+// Edmonds and Richard M. Karp.
+//
+// Ford-Fulkerson augmenting every time along a shortest path of arcs with residual capacity,
+// found by breadth-first search. Distances from the source never decrease, and every
+// augmentation saturates an arc, so there are O(VE) augmentations of O(E) each.
+
+use graph::{Adjacency, Weight};
+use super::{MaximumFlow, Network};
+
+/// Return a maximum flow from `source` to `sink` of `graph`, whose weights are the capacities
+/// of the edges, using the Edmonds-Karp algorithm.
+///
+/// ##Panics
+/// If `source` or `sink` isn't a vertex of `graph`, if they're the same vertex, or if an edge
+/// has a negative capacity.
+pub fn edmonds_karp<G>(graph: &G, source: usize, sink: usize) -> MaximumFlow<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    let mut network = Network::new(graph, source, sink, "edmonds_karp");
+
+    loop {
+        let (_, arcs) = network.search();
+
+        if arcs[sink].is_none() {
+            break;
+        }
+
+        // The arcs of the path from the sink back to the source.
+        let mut path = Vec::new();
+        let mut vertex = sink;
+        while let Some(arc) = arcs[vertex] {
+            path.push(arc);
+            vertex = network.heads[arc ^ 1];
+        }
+
+        let mut amount = network.residuals[path[0]];
+        for &arc in &path {
+            if network.residuals[arc] < amount {
+                amount = network.residuals[arc];
+            }
+        }
+
+        for &arc in &path {
+            network.push(arc, amount);
+        }
+    }
+
+    network.into_flow()
+}
+
+#[bench]
+fn bench_edmonds_karp(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::directed_from_edges(1000, random_edges(1000, 10000, 1, 1000, 1));
+
+    b.iter(|| edmonds_karp(&graph, 0, 999))
+}
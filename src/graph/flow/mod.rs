@@ -0,0 +1,403 @@
+// The algorithms of this module run on a residual network with two arcs for every edge: arc
+// `2 * i` goes along edge `i` and arc `2 * i + 1` goes back. Pushing flow along an arc takes its
+// residual capacity and gives it to its pair, so `arc ^ 1` undoes it. The arc back along a
+// directed edge starts without capacity, and along an undirected edge with the capacity of the
+// edge, so the flow on every edge is its capacity minus the residual capacity of its forward
+// arc either way.
+
+pub use self::edmonds_karp::edmonds_karp;
+pub use self::dinic::dinic;
+pub use self::push_relabel::push_relabel;
+
+use std::collections::VecDeque;
+
+use graph::{Adjacency, Weight};
+
+mod edmonds_karp;
+mod dinic;
+mod push_relabel;
+
+/// Maximum flow from a source to a sink of a graph, with a minimum cut separating them.
+#[derive(Clone, Debug)]
+pub struct MaximumFlow<W> {
+    value: W,
+    flows: Vec<W>,
+    is_source_side: Vec<bool>,
+}
+
+impl<W: Weight> MaximumFlow<W> {
+    /// Return the amount of flow from the source to the sink, which is also the capacity of the
+    /// minimum cut.
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Return the flow on every edge, indexed like the edges of the graph. The flow on an
+    /// undirected edge is negative if it goes from the target to the source.
+    pub fn flows(&self) -> &[W] {
+        &self.flows
+    }
+
+    /// Return the flow on the edge with index `edge`.
+    pub fn flow(&self, edge: usize) -> W {
+        self.flows[edge]
+    }
+
+    /// Return `true` if `vertex` is on the side of the source of the minimum cut, which holds
+    /// the vertices reachable from the source in the residual network.
+    pub fn is_source_side(&self, vertex: usize) -> bool {
+        self.is_source_side[vertex]
+    }
+
+    /// Return the vertices on the side of the source of the minimum cut in increasing order.
+    pub fn source_side(&self) -> Vec<usize> {
+        (0..self.is_source_side.len()).filter(|&vertex| self.is_source_side[vertex]).collect()
+    }
+
+    /// Return the vertices on the side of the sink of the minimum cut in increasing order.
+    pub fn sink_side(&self) -> Vec<usize> {
+        (0..self.is_source_side.len()).filter(|&vertex| !self.is_source_side[vertex]).collect()
+    }
+}
+
+/// Residual network of a graph.
+struct Network<W> {
+    source: usize,
+    sink: usize,
+    heads: Vec<usize>,
+    residuals: Vec<W>,
+    capacities: Vec<W>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> Network<W> {
+    /// Create the residual network of `graph` without flow.
+    ///
+    /// ##Panics
+    /// If `source` or `sink` isn't a vertex of `graph`, if they're the same vertex, or if an
+    /// edge has a negative capacity.
+    fn new<G: Adjacency<Weight = W>>(graph: &G,
+                                     source: usize,
+                                     sink: usize,
+                                     name: &str)
+                                     -> Network<W> {
+        let vertices = graph.vertex_count();
+
+        assert!(source < vertices,
+                "Called {} with source = {} >= vertices = {}",
+                name,
+                source,
+                vertices);
+        assert!(sink < vertices,
+                "Called {} with sink = {} >= vertices = {}",
+                name,
+                sink,
+                vertices);
+        assert!(source != sink,
+                "Called {} with source = sink = {}",
+                name,
+                source);
+
+        let zero = W::zero();
+
+        let mut network = Network {
+            source: source,
+            sink: sink,
+            heads: Vec::with_capacity(2 * graph.edge_count()),
+            residuals: Vec::with_capacity(2 * graph.edge_count()),
+            capacities: Vec::with_capacity(graph.edge_count()),
+            adjacency: vec![Vec::new(); vertices],
+        };
+
+        for (from, to, &capacity) in graph.edges() {
+            assert!(!(capacity < zero),
+                    "Called {} on a graph with a negative capacity",
+                    name);
+
+            let arc = network.heads.len();
+
+            network.heads.push(to);
+            network.heads.push(from);
+            network.residuals.push(capacity);
+            network.residuals.push(if graph.is_directed() { zero } else { capacity });
+            network.capacities.push(capacity);
+            network.adjacency[from].push(arc);
+            network.adjacency[to].push(arc + 1);
+        }
+
+        network
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Move `amount` of residual capacity from `arc` to the arc back.
+    fn push(&mut self, arc: usize, amount: W) {
+        self.residuals[arc] = self.residuals[arc] - amount;
+        self.residuals[arc ^ 1] = self.residuals[arc ^ 1] + amount;
+    }
+
+    /// Return the distances of the vertices from the source by arcs with residual capacity,
+    /// with the arcs they were reached by in a breadth-first search, or `None` for vertices
+    /// which these arcs don't reach.
+    fn search(&self) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        let zero = W::zero();
+
+        let mut distances = vec![None; self.vertex_count()];
+        let mut arcs = vec![None; self.vertex_count()];
+        distances[self.source] = Some(0);
+
+        let mut queue = VecDeque::new();
+        queue.push_back(self.source);
+
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[vertex].unwrap();
+
+            for &arc in &self.adjacency[vertex] {
+                let head = self.heads[arc];
+
+                if distances[head].is_none() && zero < self.residuals[arc] {
+                    distances[head] = Some(distance + 1);
+                    arcs[head] = Some(arc);
+                    queue.push_back(head);
+                }
+            }
+        }
+
+        (distances, arcs)
+    }
+
+    /// Return the flow of the network into the sink with the flows on the edges and the
+    /// minimum cut.
+    fn into_flow(self) -> MaximumFlow<W> {
+        let is_source_side = self.search().0.into_iter().map(|d| d.is_some()).collect();
+
+        let flows: Vec<_> = self.capacities
+            .iter()
+            .enumerate()
+            .map(|(edge, &capacity)| capacity - self.residuals[2 * edge])
+            .collect();
+
+        let mut value = W::zero();
+        for &arc in &self.adjacency[self.sink] {
+            // Flow on the edge of the arc back into the sink.
+            if arc & 1 == 1 {
+                value = value + flows[arc / 2];
+            } else {
+                value = value - flows[arc / 2];
+            }
+        }
+
+        MaximumFlow {
+            value: value,
+            flows: flows,
+            is_source_side: is_source_side,
+        }
+    }
+}
+
+/// Check that `flow` is a flow from `source` to `sink` of `graph` within the capacities, and
+/// that the capacity of its cut equals its value.
+#[cfg(test)]
+fn check_flow<G: Adjacency<Weight = i64>>(graph: &G,
+                                          source: usize,
+                                          sink: usize,
+                                          flow: &MaximumFlow<i64>) {
+    let mut excesses = vec![0; graph.vertex_count()];
+    let mut cut = 0;
+
+    for (edge, (from, to, &capacity)) in graph.edges().enumerate() {
+        let amount = flow.flow(edge);
+
+        assert!(amount <= capacity);
+        assert!(amount >= if graph.is_directed() { 0 } else { -capacity });
+
+        excesses[from] -= amount;
+        excesses[to] += amount;
+
+        if flow.is_source_side(from) && !flow.is_source_side(to) {
+            assert_eq!(capacity, amount);
+            cut += capacity;
+        } else if !flow.is_source_side(from) && flow.is_source_side(to) {
+            assert_eq!(if graph.is_directed() { 0 } else { -capacity }, amount);
+
+            if !graph.is_directed() {
+                cut += capacity;
+            }
+        }
+    }
+
+    for vertex in 0..graph.vertex_count() {
+        if vertex == source {
+            assert_eq!(-flow.value(), excesses[vertex]);
+        } else if vertex == sink {
+            assert_eq!(flow.value(), excesses[vertex]);
+        } else {
+            assert_eq!(0, excesses[vertex]);
+        }
+    }
+
+    assert!(flow.is_source_side(source));
+    assert!(!flow.is_source_side(sink));
+    assert_eq!(flow.value(), cut);
+}
+
+#[cfg(test)]
+fn test_graph() -> ::graph::Graph<(), i64> {
+    // The network of CLRS, with the maximum flow 23 from 0 to 5.
+    ::graph::Graph::directed_from_edges(6,
+                                        vec![(0, 1, 16),
+                                             (0, 2, 13),
+                                             (2, 1, 4),
+                                             (1, 3, 12),
+                                             (3, 2, 9),
+                                             (2, 4, 14),
+                                             (4, 3, 7),
+                                             (3, 5, 20),
+                                             (4, 5, 4)])
+}
+
+#[test]
+fn test_flow() {
+    let graph = test_graph();
+
+    for flow in vec![edmonds_karp(&graph, 0, 5), dinic(&graph, 0, 5), push_relabel(&graph, 0, 5)] {
+        check_flow(&graph, 0, 5, &flow);
+
+        assert_eq!(23, flow.value());
+        assert_eq!(vec![0, 1, 2, 4], flow.source_side());
+        assert_eq!(vec![3, 5], flow.sink_side());
+    }
+}
+
+#[test]
+fn test_undirected() {
+    use graph::Graph;
+
+    // The flow goes through the edge 1 - 2 against its direction.
+    let graph: Graph<(), i64> =
+        Graph::undirected_from_edges(4, vec![(0, 2, 5), (1, 2, 3), (1, 3, 4), (2, 3, 1)]);
+
+    for flow in vec![edmonds_karp(&graph, 0, 3), dinic(&graph, 0, 3), push_relabel(&graph, 0, 3)] {
+        check_flow(&graph, 0, 3, &flow);
+
+        assert_eq!(4, flow.value());
+        assert_eq!(-3, flow.flow(1));
+    }
+}
+
+#[test]
+fn test_disconnected() {
+    use graph::Graph;
+
+    let graph: Graph<(), i64> = Graph::directed_from_edges(4, vec![(0, 1, 5), (3, 2, 3)]);
+
+    for flow in vec![edmonds_karp(&graph, 0, 3), dinic(&graph, 0, 3), push_relabel(&graph, 0, 3)] {
+        assert_eq!(0, flow.value());
+        assert_eq!(&[0, 0], flow.flows());
+        assert_eq!(vec![0, 1], flow.source_side());
+    }
+}
+
+#[test]
+fn test_random() {
+    use graph::{Graph, CsrGraph, random_edges};
+
+    fn check<G: Adjacency<Weight = i64>>(graph: &G) {
+        let flows = vec![edmonds_karp(graph, 0, 29),
+                         dinic(graph, 0, 29),
+                         push_relabel(graph, 0, 29)];
+
+        for flow in &flows {
+            check_flow(graph, 0, 29, flow);
+            assert_eq!(flows[0].value(), flow.value());
+        }
+    }
+
+    for seed in 1..41 {
+        let edges = random_edges(30, 120, 0, 20, seed);
+        let undirected: Graph<(), i64> = Graph::undirected_from_edges(30, edges.iter().cloned());
+
+        check(&CsrGraph::directed_from_edges(30, edges));
+        check(&undirected);
+    }
+}
+
+#[test]
+fn test_float() {
+    use graph::Graph;
+
+    let graph: Graph<(), f64> =
+        Graph::directed_from_edges(4, vec![(0, 1, 0.5), (0, 2, 0.25), (1, 3, 1.0), (2, 3, 0.125)]);
+
+    for flow in vec![edmonds_karp(&graph, 0, 3), dinic(&graph, 0, 3), push_relabel(&graph, 0, 3)] {
+        assert_eq!(0.625, flow.value());
+    }
+}
+
+#[test]
+fn test_random_float() {
+    use graph::{CsrGraph, random_edges};
+
+    // Capacities which don't round exactly, so sums of flows are off by rounding errors.
+    for &vertices in &[20, 50] {
+        for seed in 1..400 {
+            let edges = random_edges(vertices, 4 * vertices, 0, 1000, seed)
+                .into_iter()
+                .map(|(source, target, weight)| (source, target, weight as f64 / 7.0));
+            let graph = CsrGraph::directed_from_edges(vertices, edges);
+
+            let sink = vertices - 1;
+            let total = graph.edges().map(|(_, _, &capacity)| capacity).sum::<f64>();
+            let expected = edmonds_karp(&graph, 0, sink).value();
+
+            for flow in vec![dinic(&graph, 0, sink), push_relabel(&graph, 0, sink)] {
+                assert!((expected - flow.value()).is_negligible(total));
+
+                // Flow is conserved and the cut is saturated up to rounding errors.
+                let mut excesses = vec![0.0; vertices];
+                let mut cut = 0.0;
+
+                for (edge, (from, to, &capacity)) in graph.edges().enumerate() {
+                    let amount = flow.flow(edge);
+
+                    assert!((-amount).max(amount - capacity).max(0.0).is_negligible(total));
+
+                    excesses[from] -= amount;
+                    excesses[to] += amount;
+
+                    if flow.is_source_side(from) && !flow.is_source_side(to) {
+                        assert!((capacity - amount).is_negligible(total));
+                        cut += capacity;
+                    } else if !flow.is_source_side(from) && flow.is_source_side(to) {
+                        assert!(amount.is_negligible(total));
+                    }
+                }
+
+                for vertex in 1..sink {
+                    assert!(excesses[vertex].is_negligible(total));
+                }
+
+                assert!((excesses[sink] - flow.value()).is_negligible(total));
+                assert!((cut - flow.value()).is_negligible(total));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_negative_capacity() {
+    use graph::Graph;
+
+    let graph: Graph<(), i64> = Graph::directed_from_edges(2, vec![(0, 1, -1)]);
+
+    dinic(&graph, 0, 1);
+}
+
+#[test]
+#[should_panic]
+fn test_same_source_and_sink() {
+    edmonds_karp(&test_graph(), 1, 1);
+}
@@ -0,0 +1,161 @@
+// Based on "A New Approach to the Maximum-Flow Problem" by Andrew V. Goldberg and Robert E.
+// Tarjan.
+//
+// The source starts by saturating its arcs, and vertices with more flow coming in than going out
+// push the excess along arcs to vertices one height lower. A vertex with excess but no such arc
+// is relabeled to one above its lowest neighbour by an arc with residual capacity. Excess which
+// can't reach the sink rises above the source and flows back to it.
+//
+// Vertices with excess are discharged in FIFO order, which takes O(V³). When no vertex is left at
+// a height below the source, the vertices above it can't reach the sink anymore, so this gap
+// lifts them straight over the source.
+
+use std::collections::VecDeque;
+
+use graph::{Adjacency, Weight};
+use super::{MaximumFlow, Network};
+
+/// Return a maximum flow from `source` to `sink` of `graph`, whose weights are the capacities
+/// of the edges, using the FIFO push-relabel algorithm.
+///
+/// ##Panics
+/// If `source` or `sink` isn't a vertex of `graph`, if they're the same vertex, or if an edge
+/// has a negative capacity.
+pub fn push_relabel<G>(graph: &G, source: usize, sink: usize) -> MaximumFlow<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    let mut network = Network::new(graph, source, sink, "push_relabel");
+    let zero = G::Weight::zero();
+    let vertices = network.vertex_count();
+
+    let mut heights = vec![0; vertices];
+    let mut counts = vec![0; 2 * vertices];
+    let mut excesses = vec![zero; vertices];
+    let mut current_arcs = vec![0; vertices];
+    let mut is_active = vec![false; vertices];
+    let mut queue = VecDeque::new();
+
+    // Flow out of the source, which bounds the rounding errors of the excesses.
+    let mut total = zero;
+
+    heights[source] = vertices;
+    counts[0] = vertices - 1;
+    counts[vertices] = 1;
+
+    for index in 0..network.adjacency[source].len() {
+        let arc = network.adjacency[source][index];
+        let head = network.heads[arc];
+        let amount = network.residuals[arc];
+
+        if zero < amount && head != source {
+            network.push(arc, amount);
+            excesses[head] = excesses[head] + amount;
+            total = total + amount;
+
+            if !is_active[head] && head != sink {
+                is_active[head] = true;
+                queue.push_back(head);
+            }
+        }
+    }
+
+    while let Some(vertex) = queue.pop_front() {
+        is_active[vertex] = false;
+
+        while zero < excesses[vertex] {
+            if current_arcs[vertex] == network.adjacency[vertex].len() {
+                let height = network.adjacency[vertex]
+                    .iter()
+                    .filter(|&&arc| zero < network.residuals[arc])
+                    .map(|&arc| heights[network.heads[arc]] + 1)
+                    .min();
+
+                // Excess always has a way back to the source, which is never higher than
+                // `2 * vertices - 1`. Rounding errors of floating point capacities can leave a
+                // vertex with excess but all arcs saturated, or only arcs with rounding errors
+                // left leading nowhere, so that excess is only an error and is dropped.
+                let height = match height {
+                    Some(height) if height < 2 * vertices => height,
+                    _ => {
+                        assert!(excesses[vertex].is_negligible(total),
+                                "Invariant violated: excess at vertex {} can't be pushed",
+                                vertex);
+
+                        excesses[vertex] = zero;
+                        break;
+                    }
+                };
+
+                let old_height = heights[vertex];
+
+                counts[old_height] -= 1;
+                counts[height] += 1;
+                heights[vertex] = height;
+                current_arcs[vertex] = 0;
+
+                if counts[old_height] == 0 && old_height < vertices {
+                    for other in 0..vertices {
+                        if old_height < heights[other] && heights[other] < vertices {
+                            counts[heights[other]] -= 1;
+                            counts[vertices + 1] += 1;
+                            heights[other] = vertices + 1;
+                            current_arcs[other] = 0;
+                        }
+                    }
+                }
+
+                continue;
+            }
+
+            let arc = network.adjacency[vertex][current_arcs[vertex]];
+            let head = network.heads[arc];
+            let residual = network.residuals[arc];
+
+            if zero < residual && heights[vertex] == heights[head] + 1 {
+                let amount = if residual < excesses[vertex] {
+                    residual
+                } else {
+                    excesses[vertex]
+                };
+
+                network.push(arc, amount);
+                excesses[vertex] = excesses[vertex] - amount;
+                excesses[head] = excesses[head] + amount;
+
+                if !is_active[head] && head != source && head != sink {
+                    is_active[head] = true;
+                    queue.push_back(head);
+                }
+            } else {
+                current_arcs[vertex] += 1;
+            }
+        }
+    }
+
+    network.into_flow()
+}
+
+#[test]
+fn test_gap() {
+    use graph::Graph;
+
+    // Excess stuck at 1 and 2 flows back to the source after they're lifted over it.
+    let graph: Graph<(), i64> =
+        Graph::directed_from_edges(4, vec![(0, 1, 10), (1, 2, 10), (2, 3, 1), (0, 3, 2)]);
+
+    let flow = push_relabel(&graph, 0, 3);
+
+    assert_eq!(3, flow.value());
+    assert_eq!(&[1, 1, 1, 2], flow.flows());
+    assert_eq!(vec![0, 1, 2], flow.source_side());
+}
+
+#[bench]
+fn bench_push_relabel(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::directed_from_edges(1000, random_edges(1000, 10000, 1, 1000, 1));
+
+    b.iter(|| push_relabel(&graph, 0, 999))
+}
@@ -0,0 +1,259 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+use std::slice;
+
+pub use self::adjacency_list::Graph;
+pub use self::csr::CsrGraph;
+
+pub mod shortest_path;
+pub mod mst;
+pub mod traversal;
+pub mod connectivity;
+pub mod flow;
+
+mod adjacency_list;
+mod csr;
+
+/// Trait encompassing graphs with vertices `0..vertex_count()`, which the algorithms of this
+/// module run on.
+///
+/// Edges are numbered `0..edge_count()` in the order they were added. An undirected edge is a
+/// neighbour entry of both its vertices, but it's yielded only once by `edges`.
+pub trait Adjacency {
+    /// Weight of the edges.
+    type Weight;
+
+    /// Return `true` if edges go only from their source to their target.
+    fn is_directed(&self) -> bool;
+
+    /// Return the number of vertices.
+    fn vertex_count(&self) -> usize;
+
+    /// Return the number of edges.
+    fn edge_count(&self) -> usize;
+
+    /// Return an iterator over the vertices reachable from `vertex` by one edge, with the weights
+    /// of these edges.
+    fn neighbors(&self, vertex: usize) -> Neighbors<'_, Self::Weight>;
+
+    /// Return an iterator over all edges in the order they were added.
+    fn edges(&self) -> Edges<'_, Self::Weight>;
+}
+
+/// Trait encompassing numbers usable as edge weights by the algorithms of this module.
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    /// Return the equivalent of `0` for this type.
+    fn zero() -> Self;
+
+    /// Return `true` if `self` is small enough to be a rounding error of sums of weights up to
+    /// `total`. Integers are exact, so only `0` is negligible.
+    fn is_negligible(self, total: Self) -> bool;
+}
+
+macro_rules! impl_weight {
+    ($($ty:ty),*) => (
+        $(impl Weight for $ty {
+            fn zero() -> Self {
+                0 as $ty
+            }
+
+            fn is_negligible(self, _: Self) -> bool {
+                self == 0
+            }
+        })*
+    )
+}
+
+macro_rules! impl_float_weight {
+    ($($ty:ty: $tolerance:expr),*) => (
+        $(impl Weight for $ty {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn is_negligible(self, total: Self) -> bool {
+                self.abs() <= $tolerance * total.abs()
+            }
+        })*
+    )
+}
+
+impl_weight! { i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+impl_float_weight! { f32: 1e-4, f64: 1e-9 }
+
+/// Edge going from `source` to `target`, or connecting them if the graph is undirected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Edge<E> {
+    pub source: usize,
+    pub target: usize,
+    pub weight: E,
+}
+
+/// Vertex with a key, like its distance from a source, which the heaps of the algorithms of
+/// this module hold. Candidates are ordered by their keys and then by their vertices.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Candidate<W> {
+    pub key: W,
+    pub vertex: usize,
+}
+
+/// Iterator over the neighbours of a vertex and the weights of the edges leading to them.
+pub struct Neighbors<'a, E: 'a> {
+    adjacent: slice::Iter<'a, (usize, usize)>,
+    edges: &'a [Edge<E>],
+}
+
+/// Iterator over the edges of a graph as `(source, target, weight)`.
+pub struct Edges<'a, E: 'a> {
+    edges: slice::Iter<'a, Edge<E>>,
+}
+
+impl<'a, E> Neighbors<'a, E> {
+    /// Create an iterator over `adjacent` pairs of a neighbour with the index of the edge in
+    /// `edges` leading to it.
+    fn new(adjacent: &'a [(usize, usize)], edges: &'a [Edge<E>]) -> Neighbors<'a, E> {
+        Neighbors {
+            adjacent: adjacent.iter(),
+            edges: edges,
+        }
+    }
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (usize, &'a E);
+
+    fn next(&mut self) -> Option<(usize, &'a E)> {
+        let edges = self.edges;
+
+        self.adjacent.next().map(|&(target, edge)| (target, &edges[edge].weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.adjacent.size_hint()
+    }
+}
+
+impl<'a, E> ExactSizeIterator for Neighbors<'a, E> {}
+
+impl<'a, E> Edges<'a, E> {
+    fn new(edges: &'a [Edge<E>]) -> Edges<'a, E> {
+        Edges { edges: edges.iter() }
+    }
+}
+
+impl<'a, E> Iterator for Edges<'a, E> {
+    type Item = (usize, usize, &'a E);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a E)> {
+        self.edges.next().map(|edge| (edge.source, edge.target, &edge.weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl<'a, E> ExactSizeIterator for Edges<'a, E> {}
+
+impl<W: PartialOrd> PartialOrd for Candidate<W> {
+    fn partial_cmp(&self, other: &Candidate<W>) -> Option<Ordering> {
+        match self.key.partial_cmp(&other.key) {
+            Some(Ordering::Equal) => Some(self.vertex.cmp(&other.vertex)),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<W: Ord> Ord for Candidate<W> {
+    fn cmp(&self, other: &Candidate<W>) -> Ordering {
+        self.key.cmp(&other.key).then(self.vertex.cmp(&other.vertex))
+    }
+}
+
+/// Panic unless `graph` is directed, naming the algorithm `name` called on it.
+pub(crate) fn check_directed<G: Adjacency>(graph: &G, name: &str) {
+    assert!(graph.is_directed(), "Called {} on an undirected graph", name);
+}
+
+/// Panic unless `graph` is undirected, naming the algorithm `name` called on it.
+pub(crate) fn check_undirected<G: Adjacency>(graph: &G, name: &str) {
+    assert!(!graph.is_directed(), "Called {} on a directed graph", name);
+}
+
+/// Return `count` pseudorandom edges between `vertices` vertices with weights from `min_weight`
+/// to `max_weight - 1`.
+#[cfg(test)]
+fn random_edges(vertices: usize,
+                count: usize,
+                min_weight: i64,
+                max_weight: i64,
+                seed: u64)
+                -> Vec<(usize, usize, i64)> {
+    let mut state = seed | 1;
+    let mut random = |bound: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        state % bound
+    };
+
+    (0..count)
+        .map(|_| {
+            let source = random(vertices as u64) as usize;
+            let target = random(vertices as u64) as usize;
+            let weight = min_weight + random((max_weight - min_weight) as u64) as i64;
+
+            (source, target, weight)
+        })
+        .collect()
+}
+
+/// Check that `graph` has the vertices and edges of `edges`, with neighbours in the order the
+/// edges were added.
+#[cfg(test)]
+fn check_graph<G: Adjacency<Weight = i32>>(graph: &G, directed: bool, edges: &[(usize, usize, i32)]) {
+    assert_eq!(directed, graph.is_directed());
+    assert_eq!(edges.len(), graph.edge_count());
+    assert_eq!(edges.to_vec(),
+               graph.edges().map(|(s, t, &w)| (s, t, w)).collect::<Vec<_>>());
+
+    for vertex in 0..graph.vertex_count() {
+        let mut expected = Vec::new();
+
+        for &(source, target, weight) in edges {
+            if source == vertex {
+                expected.push((target, weight));
+            }
+
+            if !directed && target == vertex {
+                expected.push((source, weight));
+            }
+        }
+
+        assert_eq!(expected.len(), graph.neighbors(vertex).len());
+        assert_eq!(expected,
+                   graph.neighbors(vertex).map(|(t, &w)| (t, w)).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_adjacency() {
+    let edges = [(0, 1, 5), (1, 2, 3), (2, 0, 1), (0, 2, 7), (3, 3, 2), (2, 1, 4)];
+
+    for &directed in &[true, false] {
+        let graph: Graph<(), i32> = if directed {
+            Graph::directed_from_edges(5, edges.iter().cloned())
+        } else {
+            Graph::undirected_from_edges(5, edges.iter().cloned())
+        };
+
+        assert_eq!(5, graph.vertex_count());
+        check_graph(&graph, directed, &edges);
+
+        let csr = CsrGraph::from(graph);
+
+        assert_eq!(5, csr.vertex_count());
+        check_graph(&csr, directed, &edges);
+    }
+}
@@ -0,0 +1,118 @@
+// Borůvka's algorithm adds the lightest edge leaving every component in rounds. Every round at
+// least halves the number of components, so there are O(log V) rounds of O(E) time. Components
+// choose their edges independently of each other, which makes rounds easy to split between
+// threads.
+//
+// Edges of equal weight are ordered by their indices, so that components never choose edges
+// which close a cycle between them.
+
+use data_structures::dsu::DisjointSet;
+use graph::{Adjacency, Weight, check_undirected};
+
+use super::SpanningTree;
+
+/// Return the minimum spanning forest of `graph`.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn boruvka<G>(graph: &G) -> SpanningTree<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    check_undirected(graph, "boruvka");
+
+    let vertices = graph.vertex_count();
+    let edges: Vec<_> = graph.edges().collect();
+
+    let mut components = DisjointSet::new(vertices);
+    let mut tree = SpanningTree::new();
+
+    loop {
+        // Index of the lightest edge leaving every component, kept at its representative.
+        let mut lightest: Vec<Option<usize>> = vec![None; vertices];
+
+        for (index, &(source, target, weight)) in edges.iter().enumerate() {
+            let (x, y) = (components.find(source), components.find(target));
+
+            if x == y {
+                continue;
+            }
+
+            for &component in &[x, y] {
+                let is_lighter = match lightest[component] {
+                    None => true,
+                    Some(other) => {
+                        let other_weight = edges[other].2;
+                        weight < other_weight || (!(other_weight < weight) && index < other)
+                    }
+                };
+
+                if is_lighter {
+                    lightest[component] = Some(index);
+                }
+            }
+        }
+
+        let mut is_changed = false;
+
+        for index in lightest.into_iter().filter_map(|index| index) {
+            let (source, target, &weight) = edges[index];
+
+            // Both components may have chosen the same edge.
+            if components.union(source, target) {
+                tree.add_edge(source, target, weight);
+                is_changed = true;
+            }
+        }
+
+        if !is_changed {
+            return tree;
+        }
+    }
+}
+
+#[test]
+fn test_boruvka() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::undirected_from_edges(6,
+                                                           vec![(0, 1, 4),
+                                                                (0, 2, 1),
+                                                                (1, 2, 2),
+                                                                (1, 3, 5),
+                                                                (2, 3, 8),
+                                                                (4, 5, 3),
+                                                                (4, 5, 1)]);
+
+    let tree = boruvka(&graph);
+
+    assert_eq!(9, tree.weight());
+    assert_eq!(4, tree.edges().len());
+}
+
+#[test]
+fn test_equal_weights() {
+    use graph::CsrGraph;
+
+    // Every spanning tree of a complete graph with equal weights is minimal, but choosing edges
+    // by weight alone could close a cycle.
+    let mut edges = Vec::new();
+    for x in 0..6 {
+        for y in 0..x {
+            edges.push((x, y, 1));
+        }
+    }
+
+    let tree = boruvka(&CsrGraph::undirected_from_edges(6, edges));
+
+    assert_eq!(5, tree.weight());
+}
+
+#[bench]
+fn bench_boruvka(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::undirected_from_edges(10000, random_edges(10000, 100000, 0, 1000, 1));
+
+    b.iter(|| boruvka(&graph))
+}
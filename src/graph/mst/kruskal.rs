@@ -0,0 +1,77 @@
+// Kruskal's algorithm adds edges from the lightest to the heaviest, skipping the ones whose
+// ends are already connected, which a disjoint set tells in O(α(V)) time. Sorting the edges
+// makes it O(E log E).
+
+use std::cmp::Ordering;
+
+use data_structures::dsu::DisjointSet;
+use graph::{Adjacency, Weight, check_undirected};
+
+use super::SpanningTree;
+
+/// Return the minimum spanning forest of `graph`.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn kruskal<G>(graph: &G) -> SpanningTree<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    check_undirected(graph, "kruskal");
+
+    let mut edges: Vec<_> = graph.edges().collect();
+    edges.sort_by(|x, y| x.2.partial_cmp(y.2).unwrap_or(Ordering::Equal));
+
+    let mut components = DisjointSet::new(graph.vertex_count());
+    let mut tree = SpanningTree::new();
+
+    for (source, target, &weight) in edges {
+        if components.union(source, target) {
+            tree.add_edge(source, target, weight);
+
+            if components.set_count() == 1 {
+                break;
+            }
+        }
+    }
+
+    tree
+}
+
+#[test]
+fn test_kruskal() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::undirected_from_edges(6,
+                                                           vec![(0, 1, 4),
+                                                                (0, 2, 1),
+                                                                (1, 2, 2),
+                                                                (1, 3, 5),
+                                                                (2, 3, 8),
+                                                                (4, 5, 3),
+                                                                (4, 5, 1)]);
+
+    let tree = kruskal(&graph);
+
+    assert_eq!(9, tree.weight());
+    assert_eq!(vec![(0, 2, 1), (4, 5, 1), (1, 2, 2), (1, 3, 5)],
+               tree.edges().iter().map(|e| (e.source, e.target, e.weight)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_float() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::undirected_from_edges(3, vec![(0, 1, 0.5), (1, 2, 0.25), (0, 2, 1.5)]);
+
+    assert_eq!(0.75, kruskal(&graph).weight());
+}
+
+#[bench]
+fn bench_kruskal(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::undirected_from_edges(10000, random_edges(10000, 100000, 0, 1000, 1));
+
+    b.iter(|| kruskal(&graph))
+}
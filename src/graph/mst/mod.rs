@@ -0,0 +1,95 @@
+pub use self::prim::{prim, prim_with_heap};
+pub use self::kruskal::kruskal;
+pub use self::boruvka::boruvka;
+
+use graph::{Edge, Weight};
+
+mod prim;
+mod kruskal;
+mod boruvka;
+
+/// Minimum spanning forest of an undirected graph, with a tree for every connected component.
+#[derive(Clone, Debug)]
+pub struct SpanningTree<W> {
+    edges: Vec<Edge<W>>,
+    weight: W,
+}
+
+impl<W: Weight> SpanningTree<W> {
+    fn new() -> SpanningTree<W> {
+        SpanningTree {
+            edges: Vec::new(),
+            weight: W::zero(),
+        }
+    }
+
+    fn add_edge(&mut self, source: usize, target: usize, weight: W) {
+        self.edges.push(Edge {
+            source: source,
+            target: target,
+            weight: weight,
+        });
+
+        self.weight = self.weight + weight;
+    }
+
+    /// Return the edges of the forest in the order they were chosen.
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// Return the total weight of the edges.
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+}
+
+/// Check that `tree` is a spanning forest of `graph` made of its edges.
+#[cfg(test)]
+fn check_spanning_tree<G: ::graph::Adjacency<Weight = i64>>(graph: &G, tree: &SpanningTree<i64>) {
+    use data_structures::dsu::DisjointSet;
+
+    let mut components = DisjointSet::new(graph.vertex_count());
+    for (source, target, _) in graph.edges() {
+        components.union(source, target);
+    }
+
+    let mut forest = DisjointSet::new(graph.vertex_count());
+    for edge in tree.edges() {
+        assert!(forest.union(edge.source, edge.target));
+        assert!(graph.neighbors(edge.source)
+            .any(|(target, &weight)| target == edge.target && weight == edge.weight));
+    }
+
+    assert_eq!(components.set_count(), forest.set_count());
+    assert_eq!(tree.edges().iter().map(|edge| edge.weight).sum::<i64>(), tree.weight());
+}
+
+#[test]
+fn test_random() {
+    use graph::{Graph, CsrGraph, random_edges};
+
+    for seed in 1..31 {
+        let edges = random_edges(40, 60, -10, 100, seed);
+        let graph: Graph<(), i64> = Graph::undirected_from_edges(40, edges.iter().cloned());
+        let csr = CsrGraph::from(graph.clone());
+
+        let trees = vec![prim(&graph), prim(&csr), kruskal(&graph), kruskal(&csr), boruvka(&csr)];
+
+        for tree in &trees {
+            check_spanning_tree(&graph, tree);
+            assert_eq!(trees[0].weight(), tree.weight());
+        }
+    }
+}
+
+#[test]
+fn test_empty() {
+    use graph::Graph;
+
+    let graph: Graph<(), i32> = Graph::undirected_from_edges(0, vec![]);
+
+    assert_eq!(0, prim(&graph).edges().len());
+    assert_eq!(0, kruskal(&graph).weight());
+    assert_eq!(0, boruvka(&graph).weight());
+}
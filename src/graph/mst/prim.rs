@@ -0,0 +1,134 @@
+// Prim's algorithm grows a tree from a vertex, always adding the lightest edge from the tree to
+// a vertex outside of it. Vertices outside of the tree are queued by the weight of their
+// lightest edge to it, which only decreases, so a Fibonacci heap makes it O(E + V log V).
+
+use data_structures::heap::{AddressablePriorityQueue, FibonacciHeap};
+use graph::{Adjacency, Candidate, Weight, check_undirected};
+
+use super::SpanningTree;
+
+/// Return the minimum spanning forest of `graph`, using a `FibonacciHeap`.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn prim<G>(graph: &G) -> SpanningTree<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    prim_with_heap::<FibonacciHeap<_>, _>(graph)
+}
+
+/// Return the minimum spanning forest of `graph`, decreasing keys of the heap `H`.
+///
+/// ##Panics
+/// If `graph` is directed.
+pub fn prim_with_heap<H, G>(graph: &G) -> SpanningTree<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: AddressablePriorityQueue<Candidate<G::Weight>> + Default
+{
+    check_undirected(graph, "prim");
+
+    let vertices = graph.vertex_count();
+    let mut tree = SpanningTree::new();
+
+    // Lightest edge from the tree to every vertex outside of it, as its other end and weight.
+    let mut lightest: Vec<Option<(usize, G::Weight)>> = vec![None; vertices];
+    let mut handles: Vec<Option<H::Handle>> = (0..vertices).map(|_| None).collect();
+    let mut is_in_tree = vec![false; vertices];
+    let mut heap = H::default();
+
+    // Grow a tree from every vertex not spanned by the earlier ones.
+    for root in 0..vertices {
+        if is_in_tree[root] {
+            continue;
+        }
+
+        let mut next = Some(root);
+
+        while let Some(vertex) = next {
+            is_in_tree[vertex] = true;
+
+            if let Some((parent, weight)) = lightest[vertex] {
+                tree.add_edge(parent, vertex, weight);
+            }
+
+            for (neighbor, &weight) in graph.neighbors(vertex) {
+                if is_in_tree[neighbor] {
+                    continue;
+                }
+
+                let candidate = Candidate {
+                    key: weight,
+                    vertex: neighbor,
+                };
+
+                match lightest[neighbor] {
+                    None => handles[neighbor] = Some(heap.push_with_handle(candidate)),
+                    Some((_, old_weight)) if weight < old_weight => {
+                        heap.decrease_key(handles[neighbor].as_ref().unwrap(), candidate)
+                    }
+                    Some(_) => continue,
+                }
+
+                lightest[neighbor] = Some((vertex, weight));
+            }
+
+            next = heap.pop().map(|candidate| candidate.vertex);
+        }
+    }
+
+    tree
+}
+
+#[test]
+fn test_prim() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::undirected_from_edges(6,
+                                                           vec![(0, 1, 4),
+                                                                (0, 2, 1),
+                                                                (1, 2, 2),
+                                                                (1, 3, 5),
+                                                                (2, 3, 8),
+                                                                (4, 5, 3),
+                                                                (4, 5, 1)]);
+
+    let tree = prim(&graph);
+
+    assert_eq!(9, tree.weight());
+    assert_eq!(vec![(0, 2, 1), (2, 1, 2), (1, 3, 5), (4, 5, 1)],
+               tree.edges().iter().map(|e| (e.source, e.target, e.weight)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_heaps() {
+    use data_structures::heap::{PairingHeap, DaryHeap, ArenaFibonacciHeap};
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::undirected_from_edges(50, random_edges(50, 200, 0, 100, 7));
+    let weight = prim(&graph).weight();
+
+    assert_eq!(weight, prim_with_heap::<PairingHeap<_>, _>(&graph).weight());
+    assert_eq!(weight, prim_with_heap::<DaryHeap<_>, _>(&graph).weight());
+    assert_eq!(weight, prim_with_heap::<ArenaFibonacciHeap<_>, _>(&graph).weight());
+}
+
+#[test]
+#[should_panic]
+fn test_directed() {
+    use graph::Graph;
+
+    let graph: Graph<(), i32> = Graph::directed_from_edges(2, vec![(0, 1, 1)]);
+
+    prim(&graph);
+}
+
+#[bench]
+fn bench_prim(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::undirected_from_edges(10000, random_edges(10000, 100000, 0, 1000, 1));
+
+    b.iter(|| prim(&graph))
+}
@@ -0,0 +1,288 @@
+// Bellman-Ford relaxes every edge in rounds. After `i` rounds, every vertex has its distance
+// if its shortest path has at most `i` edges, so `V - 1` rounds are enough unless a negative
+// cycle is reachable. SPFA only relaxes the edges of vertices whose distances changed, which
+// is the same in the worst case but usually much faster.
+//
+// Any cycle among the predecessors has a negative weight, and one forms when a negative cycle
+// is reachable, so the predecessors are searched for a cycle to return as its witness.
+
+use std::collections::VecDeque;
+
+use graph::{Adjacency, Weight};
+
+use super::ShortestPaths;
+
+/// Return the shortest paths from `source` in `graph`, or the vertices of a negative cycle
+/// reachable from it, in the order of its edges.
+///
+/// Edges of undirected graphs go both ways, so a negative edge makes a negative cycle.
+///
+/// ##Panics
+/// If `source` isn't a vertex of `graph`.
+pub fn bellman_ford<G>(graph: &G, source: usize) -> Result<ShortestPaths<G::Weight>, Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    assert!(source < graph.vertex_count(),
+            "Called bellman_ford with source = {} >= vertices = {}",
+            source,
+            graph.vertex_count());
+
+    let mut paths = ShortestPaths::new(source, graph.vertex_count(), G::Weight::zero());
+    relax_rounds(graph, &mut paths.distances, &mut paths.predecessors).map(|_| paths)
+}
+
+/// Return the shortest paths from `source` in `graph` like `bellman_ford`, using the Shortest
+/// Path Faster Algorithm.
+///
+/// ##Panics
+/// If `source` isn't a vertex of `graph`.
+pub fn spfa<G>(graph: &G, source: usize) -> Result<ShortestPaths<G::Weight>, Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    assert!(source < graph.vertex_count(),
+            "Called spfa with source = {} >= vertices = {}",
+            source,
+            graph.vertex_count());
+
+    let vertices = graph.vertex_count();
+    let mut paths = ShortestPaths::new(source, vertices, G::Weight::zero());
+
+    // Number of edges of the path to every vertex. A path of `V` edges repeats a vertex.
+    let mut lengths = vec![0; vertices];
+    let mut is_queued = vec![false; vertices];
+    let mut queue = VecDeque::new();
+
+    queue.push_back(source);
+    is_queued[source] = true;
+
+    while let Some(vertex) = queue.pop_front() {
+        is_queued[vertex] = false;
+
+        let distance = paths.distances[vertex].unwrap();
+
+        for (neighbor, &weight) in graph.neighbors(vertex) {
+            let new_distance = distance + weight;
+
+            if paths.distances[neighbor].map_or(false, |old_distance| !(new_distance < old_distance)) {
+                continue;
+            }
+
+            paths.distances[neighbor] = Some(new_distance);
+            paths.predecessors[neighbor] = Some(vertex);
+            lengths[neighbor] = lengths[vertex] + 1;
+
+            if lengths[neighbor] >= vertices {
+                if let Some(cycle) = find_cycle(&paths.predecessors) {
+                    return Err(cycle);
+                }
+            }
+
+            if !is_queued[neighbor] {
+                queue.push_back(neighbor);
+                is_queued[neighbor] = true;
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Relax the edges of `graph` from the vertices with `distances` until no distance changes.
+/// Return the vertices of a negative cycle if they keep changing.
+pub(super) fn relax_rounds<G>(graph: &G,
+                              distances: &mut [Option<G::Weight>],
+                              predecessors: &mut [Option<usize>])
+                              -> Result<(), Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    for _ in 0..graph.vertex_count() {
+        let mut is_changed = false;
+
+        for vertex in 0..graph.vertex_count() {
+            let distance = match distances[vertex] {
+                None => continue,
+                Some(distance) => distance,
+            };
+
+            for (neighbor, &weight) in graph.neighbors(vertex) {
+                let new_distance = distance + weight;
+
+                if distances[neighbor].map_or(true, |old_distance| new_distance < old_distance) {
+                    distances[neighbor] = Some(new_distance);
+                    predecessors[neighbor] = Some(vertex);
+                    is_changed = true;
+                }
+            }
+        }
+
+        if !is_changed {
+            return Ok(());
+        }
+    }
+
+    // Distances changed in round `V`, so a path to some vertex has `V` edges and repeats a
+    // vertex on a negative cycle.
+    Err(find_cycle(predecessors).expect("Predecessors of a negative cycle form a cycle"))
+}
+
+/// Return the vertices of a cycle among `predecessors` in the order of its edges, or `None` if
+/// they form a forest.
+fn find_cycle(predecessors: &[Option<usize>]) -> Option<Vec<usize>> {
+    // The walk which visited every vertex, so walks stop at vertices visited by earlier ones.
+    let mut walks = vec![None; predecessors.len()];
+
+    for start in 0..predecessors.len() {
+        let mut vertex = start;
+
+        while walks[vertex].is_none() {
+            walks[vertex] = Some(start);
+
+            vertex = match predecessors[vertex] {
+                None => break,
+                Some(predecessor) => predecessor,
+            };
+
+            if walks[vertex] == Some(start) {
+                let mut cycle = vec![vertex];
+                let mut current = predecessors[vertex].unwrap();
+
+                while current != vertex {
+                    cycle.push(current);
+                    current = predecessors[current].unwrap();
+                }
+
+                cycle.reverse();
+                return Some(cycle);
+            }
+        }
+    }
+
+    None
+}
+
+/// Check that `cycle` is a cycle of edges of `graph` with a negative weight.
+#[cfg(test)]
+fn check_negative_cycle<G: Adjacency<Weight = i64>>(graph: &G, cycle: &[usize]) {
+    let mut weight = 0;
+
+    for (i, &vertex) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+
+        weight += graph.neighbors(vertex)
+            .filter(|&(target, _)| target == next)
+            .map(|(_, &weight)| weight)
+            .min()
+            .expect("Cycle follows edges of the graph");
+    }
+
+    assert!(weight < 0);
+}
+
+#[test]
+fn test_bellman_ford() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::directed_from_edges(5,
+                                                         vec![(0, 1, 4),
+                                                              (0, 2, 5),
+                                                              (2, 1, -3),
+                                                              (1, 3, 2),
+                                                              (3, 4, -1)]);
+
+    for paths in vec![bellman_ford(&graph, 0), spfa(&graph, 0)] {
+        let paths = paths.unwrap();
+
+        assert_eq!(&[Some(0), Some(2), Some(5), Some(4), Some(3)], paths.distances());
+        assert_eq!(Some(vec![0, 2, 1, 3, 4]), paths.path(4));
+    }
+}
+
+#[test]
+fn test_negative_cycle() {
+    use graph::Graph;
+
+    let graph: Graph<(), i64> = Graph::directed_from_edges(6,
+                                                           vec![(0, 1, 1),
+                                                                (1, 2, 1),
+                                                                (2, 3, -2),
+                                                                (3, 4, 1),
+                                                                (4, 2, -1),
+                                                                (5, 0, 1)]);
+
+    for result in vec![bellman_ford(&graph, 0), spfa(&graph, 0)] {
+        let mut cycle = result.unwrap_err();
+        check_negative_cycle(&graph, &cycle);
+
+        cycle.sort();
+        assert_eq!(vec![2, 3, 4], cycle);
+    }
+
+    // Negative cycles which aren't reachable from the source don't matter.
+    let graph: Graph<(), i64> = Graph::directed_from_edges(3, vec![(1, 2, -1), (2, 1, -1)]);
+
+    assert!(bellman_ford(&graph, 0).is_ok());
+    assert!(spfa(&graph, 0).is_ok());
+}
+
+#[test]
+fn test_undirected_negative_edge() {
+    use graph::Graph;
+
+    let graph: Graph<(), i64> = Graph::undirected_from_edges(3, vec![(0, 1, 2), (1, 2, -1)]);
+
+    let mut cycle = bellman_ford(&graph, 0).unwrap_err();
+    cycle.sort();
+
+    assert_eq!(vec![1, 2], cycle);
+}
+
+#[test]
+fn test_random() {
+    use graph::{Graph, random_edges};
+    use super::{reference_distances, check_paths};
+
+    let mut cycles = 0;
+
+    for seed in 1..51 {
+        let edges = random_edges(30, 60, -3, 20, seed);
+        let graph: Graph<(), i64> = Graph::directed_from_edges(30, edges.iter().cloned());
+
+        match (bellman_ford(&graph, 0), spfa(&graph, 0)) {
+            (Ok(x), Ok(y)) => {
+                assert_eq!(&reference_distances(30, &edges, true, 0)[..], x.distances());
+                assert_eq!(x.distances(), y.distances());
+                check_paths(&graph, &x);
+                check_paths(&graph, &y);
+            }
+            (Err(x), Err(y)) => {
+                check_negative_cycle(&graph, &x);
+                check_negative_cycle(&graph, &y);
+                cycles += 1;
+            }
+            _ => panic!("Only one of bellman_ford and spfa found a negative cycle"),
+        }
+    }
+
+    assert!(cycles > 0 && cycles < 50);
+}
+
+#[bench]
+fn bench_bellman_ford(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::directed_from_edges(1000, random_edges(1000, 10000, 0, 1000, 1));
+
+    b.iter(|| bellman_ford(&graph, 0))
+}
+
+#[bench]
+fn bench_spfa(b: &mut ::test::Bencher) {
+    use graph::{CsrGraph, random_edges};
+
+    let graph = CsrGraph::directed_from_edges(1000, random_edges(1000, 10000, 0, 1000, 1));
+
+    b.iter(|| spfa(&graph, 0))
+}
@@ -0,0 +1,395 @@
+// Dijkstra's algorithm and A*, which is Dijkstra's algorithm ordering vertices by their
+// distance plus an estimate of the distance left to the target.
+//
+// Vertices are queued in a heap by the length of the best path found to them so far. With an
+// addressable heap, a shorter path decreases the key of the queued vertex, so every vertex is
+// queued once and a Fibonacci heap makes the search O(E + V log V). Any other heap gets the
+// vertex pushed again instead, and outdated entries are skipped when they're popped, which
+// makes the search O(E log E).
+
+use data_structures::heap::{PriorityQueue, AddressablePriorityQueue, FibonacciHeap};
+use graph::{Adjacency, Candidate, Weight};
+
+use super::ShortestPaths;
+
+/// Return the shortest paths from `source` in `graph`, using a `FibonacciHeap`.
+///
+/// ##Panics
+/// If `source` isn't a vertex of `graph`, or `graph` has a negative weight reachable from it.
+pub fn dijkstra<G>(graph: &G, source: usize) -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    dijkstra_with_heap::<FibonacciHeap<_>, _>(graph, source)
+}
+
+/// Return the shortest paths from `source` in `graph`, decreasing keys of the heap `H`.
+///
+/// ##Panics
+/// If `source` isn't a vertex of `graph`, or `graph` has a negative weight reachable from it.
+pub fn dijkstra_with_heap<H, G>(graph: &G, source: usize) -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: AddressablePriorityQueue<Candidate<G::Weight>> + Default
+{
+    let mut queue = AddressableQueue::new(H::default(), graph.vertex_count());
+    search(graph, source, None, |_| G::Weight::zero(), &mut queue)
+}
+
+/// Return the shortest paths from `source` in `graph`, pushing vertices again into the heap `H`
+/// when shorter paths to them are found. This works with any heap, like `BinaryHeap`.
+///
+/// ##Panics
+/// If `source` isn't a vertex of `graph`, or `graph` has a negative weight reachable from it.
+pub fn dijkstra_with_lazy_heap<H, G>(graph: &G, source: usize) -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: PriorityQueue<Candidate<G::Weight>> + Default
+{
+    let mut queue = LazyQueue { heap: H::default() };
+    search(graph, source, None, |_| G::Weight::zero(), &mut queue)
+}
+
+/// Search `graph` for the shortest path from `source` to `target`, using a `FibonacciHeap`.
+///
+/// `heuristic` estimates the distance from a vertex to `target`. It has to be consistent:
+/// `0` for `target`, and never larger than the weight of an edge plus its estimate for the
+/// other end of the edge. The search stops when `target` is reached, so the returned distances
+/// are exact only for `target` and the vertices that were closer than it. The other distances
+/// are lengths of paths which may not be the shortest ones.
+///
+/// ##Panics
+/// If `source` or `target` isn't a vertex of `graph`, or `graph` has a negative weight
+/// reachable from `source`.
+pub fn astar<G, F>(graph: &G, source: usize, target: usize, heuristic: F) -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          F: Fn(usize) -> G::Weight
+{
+    astar_with_heap::<FibonacciHeap<_>, _, _>(graph, source, target, heuristic)
+}
+
+/// Search `graph` for the shortest path from `source` to `target` like `astar`, decreasing keys
+/// of the heap `H`.
+///
+/// ##Panics
+/// If `source` or `target` isn't a vertex of `graph`, or `graph` has a negative weight
+/// reachable from `source`.
+pub fn astar_with_heap<H, G, F>(graph: &G,
+                                source: usize,
+                                target: usize,
+                                heuristic: F)
+                                -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: AddressablePriorityQueue<Candidate<G::Weight>> + Default,
+          F: Fn(usize) -> G::Weight
+{
+    check_target(graph, target);
+
+    let mut queue = AddressableQueue::new(H::default(), graph.vertex_count());
+    search(graph, source, Some(target), heuristic, &mut queue)
+}
+
+/// Search `graph` for the shortest path from `source` to `target` like `astar`, pushing
+/// vertices again into the heap `H` when shorter paths to them are found.
+///
+/// ##Panics
+/// If `source` or `target` isn't a vertex of `graph`, or `graph` has a negative weight
+/// reachable from `source`.
+pub fn astar_with_lazy_heap<H, G, F>(graph: &G,
+                                     source: usize,
+                                     target: usize,
+                                     heuristic: F)
+                                     -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: PriorityQueue<Candidate<G::Weight>> + Default,
+          F: Fn(usize) -> G::Weight
+{
+    check_target(graph, target);
+
+    let mut queue = LazyQueue { heap: H::default() };
+    search(graph, source, Some(target), heuristic, &mut queue)
+}
+
+fn check_target<G: Adjacency>(graph: &G, target: usize) {
+    assert!(target < graph.vertex_count(),
+            "Called astar with target = {} >= vertices = {}",
+            target,
+            graph.vertex_count());
+}
+
+/// Queue of the vertices to visit next, ordered by their keys.
+trait Queue<W> {
+    /// Add `vertex` with `key`, or lower its key if `is_queued`.
+    fn push(&mut self, vertex: usize, key: W, is_queued: bool);
+
+    /// Remove the vertex with the smallest key and return it. It may have been returned before.
+    fn pop(&mut self) -> Option<usize>;
+}
+
+/// Queue decreasing keys of queued vertices through their handles.
+struct AddressableQueue<H, D> {
+    heap: H,
+    handles: Vec<Option<D>>,
+}
+
+/// Queue pushing vertices again instead of decreasing their keys.
+struct LazyQueue<H> {
+    heap: H,
+}
+
+impl<H, D> AddressableQueue<H, D> {
+    fn new(heap: H, vertices: usize) -> AddressableQueue<H, D> {
+        AddressableQueue {
+            heap: heap,
+            handles: (0..vertices).map(|_| None).collect(),
+        }
+    }
+}
+
+impl<W, H: AddressablePriorityQueue<Candidate<W>>> Queue<W> for AddressableQueue<H, H::Handle> {
+    fn push(&mut self, vertex: usize, key: W, is_queued: bool) {
+        let candidate = Candidate {
+            key: key,
+            vertex: vertex,
+        };
+
+        if is_queued {
+            self.heap.decrease_key(self.handles[vertex].as_ref().unwrap(), candidate);
+        } else {
+            self.handles[vertex] = Some(self.heap.push_with_handle(candidate));
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.heap.pop().map(|candidate| candidate.vertex)
+    }
+}
+
+impl<W, H: PriorityQueue<Candidate<W>>> Queue<W> for LazyQueue<H> {
+    fn push(&mut self, vertex: usize, key: W, _: bool) {
+        self.heap.push(Candidate {
+            key: key,
+            vertex: vertex,
+        });
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.heap.pop().map(|candidate| candidate.vertex)
+    }
+}
+
+/// Visit vertices of `graph` from `source` in the order of their distances plus `heuristic`,
+/// until `target` or all reachable vertices are visited.
+fn search<G, F, Q>(graph: &G,
+                   source: usize,
+                   target: Option<usize>,
+                   heuristic: F,
+                   queue: &mut Q)
+                   -> ShortestPaths<G::Weight>
+    where G: Adjacency,
+          G::Weight: Weight,
+          F: Fn(usize) -> G::Weight,
+          Q: Queue<G::Weight>
+{
+    assert!(source < graph.vertex_count(),
+            "Called dijkstra with source = {} >= vertices = {}",
+            source,
+            graph.vertex_count());
+
+    let zero = G::Weight::zero();
+    let mut paths = ShortestPaths::new(source, graph.vertex_count(), zero);
+    let mut is_visited = vec![false; graph.vertex_count()];
+
+    queue.push(source, heuristic(source), false);
+
+    while let Some(vertex) = queue.pop() {
+        if is_visited[vertex] {
+            continue;
+        }
+
+        is_visited[vertex] = true;
+        if Some(vertex) == target {
+            break;
+        }
+
+        let distance = paths.distances[vertex].unwrap();
+
+        for (neighbor, &weight) in graph.neighbors(vertex) {
+            assert!(!(weight < zero),
+                    "Called dijkstra on a graph with a negative weight");
+
+            if is_visited[neighbor] {
+                continue;
+            }
+
+            let new_distance = distance + weight;
+            let old_distance = paths.distances[neighbor];
+
+            if old_distance.map_or(true, |old_distance| new_distance < old_distance) {
+                paths.distances[neighbor] = Some(new_distance);
+                paths.predecessors[neighbor] = Some(vertex);
+
+                queue.push(neighbor,
+                           new_distance + heuristic(neighbor),
+                           old_distance.is_some());
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+fn check_dijkstra<H>()
+    where H: AddressablePriorityQueue<Candidate<i64>> + Default
+{
+    use graph::Graph;
+    use graph::random_edges;
+    use super::{reference_distances, check_paths};
+
+    for seed in 1..11 {
+        let edges = random_edges(50, 200, 0, 20, seed);
+
+        for &directed in &[true, false] {
+            let graph: Graph<(), i64> = if directed {
+                Graph::directed_from_edges(50, edges.iter().cloned())
+            } else {
+                Graph::undirected_from_edges(50, edges.iter().cloned())
+            };
+
+            let expected = reference_distances(50, &edges, directed, 0);
+
+            let paths = dijkstra_with_heap::<H, _>(&graph, 0);
+            assert_eq!(&expected[..], paths.distances());
+            check_paths(&graph, &paths);
+        }
+    }
+}
+
+#[cfg(test)]
+fn check_dijkstra_lazy<H>()
+    where H: PriorityQueue<Candidate<i64>> + Default
+{
+    use graph::CsrGraph;
+    use graph::random_edges;
+    use super::{reference_distances, check_paths};
+
+    for seed in 1..11 {
+        let edges = random_edges(50, 200, 0, 20, seed);
+        let graph = CsrGraph::directed_from_edges(50, edges.iter().cloned());
+
+        let paths = dijkstra_with_lazy_heap::<H, _>(&graph, 0);
+        assert_eq!(&reference_distances(50, &edges, true, 0)[..], paths.distances());
+        check_paths(&graph, &paths);
+    }
+}
+
+#[test]
+fn test_dijkstra() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::directed_from_edges(5,
+                                                         vec![(0, 1, 4),
+                                                              (0, 2, 1),
+                                                              (2, 1, 2),
+                                                              (1, 3, 1),
+                                                              (2, 3, 5),
+                                                              (4, 0, 1)]);
+
+    let paths = dijkstra(&graph, 0);
+
+    assert_eq!(&[Some(0), Some(3), Some(1), Some(4), None], paths.distances());
+    assert_eq!(Some(vec![0, 2, 1, 3]), paths.path(3));
+    assert_eq!(None, paths.path(4));
+}
+
+#[test]
+fn test_dijkstra_float() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::undirected_from_edges(3, vec![(0, 1, 0.5), (1, 2, 0.25), (0, 2, 1.0)]);
+
+    let paths = dijkstra(&graph, 0);
+
+    assert_eq!(Some(0.75), paths.distance(2));
+    assert_eq!(Some(1), paths.predecessor(2));
+}
+
+#[test]
+fn test_dijkstra_heaps() {
+    use data_structures::heap::*;
+
+    check_dijkstra::<FibonacciHeap<_>>();
+    check_dijkstra::<ArenaFibonacciHeap<_>>();
+    check_dijkstra::<PairingHeap<_>>();
+    check_dijkstra::<DaryHeap<_>>();
+
+    check_dijkstra_lazy::<BinaryHeap<_>>();
+    check_dijkstra_lazy::<BinomialHeap<_>>();
+    check_dijkstra_lazy::<FibonacciHeap<_>>();
+    check_dijkstra_lazy::<UnsafeFibonacciHeap<_>>();
+}
+
+#[test]
+#[should_panic]
+fn test_dijkstra_negative_weight() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::directed_from_edges(2, vec![(0, 1, -1)]);
+
+    dijkstra(&graph, 0);
+}
+
+#[test]
+fn test_astar() {
+    use data_structures::heap::BinaryHeap;
+    use graph::Graph;
+
+    // Grid with the cell `(x, y)` at vertex `y * 10 + x`.
+    let mut edges = Vec::new();
+    for y in 0..10 {
+        for x in 0..10 {
+            let vertex = y * 10 + x;
+
+            if x < 9 {
+                edges.push((vertex, vertex + 1, 1));
+            }
+
+            if y < 9 {
+                edges.push((vertex, vertex + 10, 1));
+            }
+        }
+    }
+
+    let graph: Graph<(), i32> = Graph::undirected_from_edges(100, edges);
+    let manhattan = |vertex: usize| {
+        let (x, y) = ((vertex % 10) as i32, (vertex / 10) as i32);
+
+        (9 - x).abs() + y.abs()
+    };
+
+    let paths = astar(&graph, 0, 9, manhattan);
+    let lazy_paths = astar_with_lazy_heap::<BinaryHeap<_>, _, _>(&graph, 0, 9, manhattan);
+
+    assert_eq!(Some(9), paths.distance(9));
+    assert_eq!(Some(9), lazy_paths.distance(9));
+    assert_eq!(Some((0..10).collect()), paths.path(9));
+
+    // Only the first row is visited, so cells below the second one are never reached.
+    assert_eq!(Some(18), dijkstra(&graph, 0).distance(99));
+    assert_eq!(None, paths.distance(20));
+    assert_eq!(None, lazy_paths.distance(99));
+}
+
+#[bench]
+fn bench_dijkstra(b: &mut ::test::Bencher) {
+    use graph::CsrGraph;
+    use graph::random_edges;
+
+    let graph = CsrGraph::directed_from_edges(10000, random_edges(10000, 100000, 0, 1000, 1));
+
+    b.iter(|| dijkstra(&graph, 0))
+}
@@ -0,0 +1,224 @@
+// Floyd-Warshall finds the shortest paths between all pairs of vertices in O(V^3) time. After
+// round `k`, the distance from `i` to `j` is the shortest one over paths whose inner vertices
+// are smaller than `k`. Every pair also keeps the vertex after `i` on its path, which gives the
+// whole path by following these vertices to `j`.
+
+use graph::{Adjacency, Weight};
+
+use super::bellman_ford::relax_rounds;
+
+/// Distances between all pairs of vertices of a graph, with the shortest paths between them.
+#[derive(Clone, Debug)]
+pub struct AllPairsShortestPaths<W> {
+    vertices: usize,
+    distances: Vec<Option<W>>,
+    successors: Vec<Option<usize>>,
+}
+
+/// Return the shortest paths between all pairs of vertices of `graph`, or the vertices of a
+/// negative cycle in the order of its edges.
+pub fn floyd_warshall<G>(graph: &G) -> Result<AllPairsShortestPaths<G::Weight>, Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    let n = graph.vertex_count();
+    let zero = G::Weight::zero();
+
+    let mut distances = vec![None; n * n];
+    let mut successors = vec![None; n * n];
+
+    for vertex in 0..n {
+        distances[vertex * n + vertex] = Some(zero);
+        successors[vertex * n + vertex] = Some(vertex);
+    }
+
+    for vertex in 0..n {
+        for (neighbor, &weight) in graph.neighbors(vertex) {
+            let pair = vertex * n + neighbor;
+
+            if distances[pair].map_or(true, |distance| weight < distance) {
+                distances[pair] = Some(weight);
+                successors[pair] = Some(neighbor);
+            }
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let to_k = match distances[i * n + k] {
+                None => continue,
+                Some(distance) => distance,
+            };
+
+            for j in 0..n {
+                let from_k = match distances[k * n + j] {
+                    None => continue,
+                    Some(distance) => distance,
+                };
+
+                let distance = to_k + from_k;
+
+                if distances[i * n + j].map_or(true, |old_distance| distance < old_distance) {
+                    distances[i * n + j] = Some(distance);
+                    successors[i * n + j] = successors[i * n + k];
+                }
+            }
+        }
+
+        // A vertex on a negative cycle has a negative distance to itself. Paths through such
+        // cycles are meaningless, and going on would double their distances every round until
+        // they overflow, so the cycle is found again with Bellman-Ford to return its vertices.
+        if (0..n).any(|vertex| distances[vertex * n + vertex].unwrap() < zero) {
+            let mut potentials = vec![Some(zero); n];
+            let mut predecessors = vec![None; n];
+
+            return Err(relax_rounds(graph, &mut potentials, &mut predecessors)
+                .err()
+                .expect("Bellman-Ford finds the negative cycle found by Floyd-Warshall"));
+        }
+    }
+
+    Ok(AllPairsShortestPaths {
+        vertices: n,
+        distances: distances,
+        successors: successors,
+    })
+}
+
+impl<W: Copy> AllPairsShortestPaths<W> {
+    /// Return the distance from `source` to `target`, or `None` if it's unreachable.
+    pub fn distance(&self, source: usize, target: usize) -> Option<W> {
+        self.distances[source * self.vertices + target]
+    }
+
+    /// Return the vertex after `source` on the path to `target`, or `None` if it's
+    /// unreachable. The successor of a vertex on the path to itself is itself.
+    pub fn successor(&self, source: usize, target: usize) -> Option<usize> {
+        self.successors[source * self.vertices + target]
+    }
+
+    /// Return the vertices of the path from `source` to `target`, or `None` if it's
+    /// unreachable.
+    pub fn path(&self, source: usize, target: usize) -> Option<Vec<usize>> {
+        if self.successor(source, target).is_none() {
+            return None;
+        }
+
+        let mut path = vec![source];
+        let mut vertex = source;
+
+        while vertex != target {
+            vertex = self.successor(vertex, target).unwrap();
+            path.push(vertex);
+        }
+
+        Some(path)
+    }
+}
+
+#[test]
+fn test_floyd_warshall() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::directed_from_edges(4,
+                                                         vec![(0, 1, -2),
+                                                              (1, 2, 3),
+                                                              (2, 0, 4),
+                                                              (0, 2, 2),
+                                                              (3, 2, -5)]);
+
+    let paths = floyd_warshall(&graph).unwrap();
+
+    assert_eq!(Some(1), paths.distance(0, 2));
+    assert_eq!(Some(-3), paths.distance(3, 1));
+    assert_eq!(None, paths.distance(0, 3));
+    assert_eq!(Some(0), paths.distance(3, 3));
+
+    assert_eq!(Some(vec![3, 2, 0, 1]), paths.path(3, 1));
+    assert_eq!(Some(vec![1]), paths.path(1, 1));
+    assert_eq!(None, paths.path(2, 3));
+}
+
+#[test]
+fn test_undirected() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::undirected_from_edges(4, vec![(0, 1, 1.5), (1, 2, 1.0), (0, 2, 3.0)]);
+
+    let paths = floyd_warshall(&graph).unwrap();
+
+    assert_eq!(Some(2.5), paths.distance(2, 0));
+    assert_eq!(Some(vec![2, 1, 0]), paths.path(2, 0));
+    assert_eq!(None, paths.distance(3, 0));
+}
+
+#[test]
+fn test_negative_cycle() {
+    use graph::Graph;
+
+    let graph: Graph<(), i32> = Graph::directed_from_edges(4,
+                                                           vec![(0, 1, 1),
+                                                                (1, 2, -1),
+                                                                (2, 3, -1),
+                                                                (3, 1, 1)]);
+
+    let mut cycle = floyd_warshall(&graph).unwrap_err();
+    cycle.sort();
+
+    assert_eq!(vec![1, 2, 3], cycle);
+}
+
+#[test]
+fn test_negative_complete() {
+    use graph::CsrGraph;
+
+    // Distances would shrink exponentially over the rounds and overflow.
+    let edges = (0..40).flat_map(|s| (0..40).filter(move |&t| t != s).map(move |t| (s, t, -1)));
+    let graph = CsrGraph::directed_from_edges(40, edges);
+
+    let cycle = floyd_warshall(&graph).unwrap_err();
+
+    assert!(cycle.len() >= 2);
+    for (i, &vertex) in cycle.iter().enumerate() {
+        assert!(cycle[i + 1..].iter().all(|&other| other != vertex));
+    }
+}
+
+#[test]
+fn test_random() {
+    use graph::{Graph, random_edges};
+    use super::johnson;
+
+    for seed in 1..21 {
+        let edges = random_edges(20, 40, -2, 20, seed);
+        let graph: Graph<(), i64> = Graph::directed_from_edges(20, edges);
+
+        let (paths, all_paths) = match (floyd_warshall(&graph), johnson(&graph)) {
+            (Ok(paths), Ok(all_paths)) => (paths, all_paths),
+            (Err(_), Err(_)) => continue,
+            _ => panic!("Only one of floyd_warshall and johnson found a negative cycle"),
+        };
+
+        for source in 0..20 {
+            for target in 0..20 {
+                let distance = all_paths[source].distance(target);
+                assert_eq!(distance, paths.distance(source, target));
+
+                // Sum the weights of the path edges, taking the lightest of parallel edges.
+                let weight = paths.path(source, target).map(|path| {
+                    path.windows(2)
+                        .map(|pair| {
+                            graph.neighbors(pair[0])
+                                .filter(|&(t, _)| t == pair[1])
+                                .map(|(_, &w)| w)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum::<i64>()
+                });
+
+                assert_eq!(distance, weight);
+            }
+        }
+    }
+}
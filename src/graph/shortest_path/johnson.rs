@@ -0,0 +1,136 @@
+// Based on "Efficient Algorithms for Shortest Paths in Sparse Networks" by Donald B. Johnson.
+//
+// Bellman-Ford from a virtual vertex with an edge of weight 0 to every vertex gives every vertex
+// a potential `h(v) <= 0`, with `h(v) <= h(u) + w(u, v)` for every edge. Reweighting the edges
+// to `w(u, v) + h(u) - h(v)` makes them non-negative without changing which paths are the
+// shortest, so Dijkstra runs from every vertex in O(V E + V^2 log V) time with a Fibonacci heap.
+
+use data_structures::heap::{AddressablePriorityQueue, FibonacciHeap};
+use graph::{Adjacency, Candidate, CsrGraph, Weight};
+
+use super::{ShortestPaths, dijkstra_with_heap};
+use super::bellman_ford::relax_rounds;
+
+/// Return the shortest paths from every vertex of `graph`, or the vertices of a negative cycle
+/// in the order of its edges. Uses a `FibonacciHeap`.
+pub fn johnson<G>(graph: &G) -> Result<Vec<ShortestPaths<G::Weight>>, Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight
+{
+    johnson_with_heap::<FibonacciHeap<_>, _>(graph)
+}
+
+/// Return the shortest paths from every vertex of `graph` like `johnson`, running Dijkstra with
+/// the heap `H`.
+pub fn johnson_with_heap<H, G>(graph: &G) -> Result<Vec<ShortestPaths<G::Weight>>, Vec<usize>>
+    where G: Adjacency,
+          G::Weight: Weight,
+          H: AddressablePriorityQueue<Candidate<G::Weight>> + Default
+{
+    let vertices = graph.vertex_count();
+    let zero = G::Weight::zero();
+
+    let mut potentials = vec![Some(zero); vertices];
+    let mut predecessors = vec![None; vertices];
+    relax_rounds(graph, &mut potentials, &mut predecessors)?;
+
+    let potentials: Vec<_> = potentials.into_iter().map(Option::unwrap).collect();
+
+    // Edges of undirected graphs are reweighted differently in both directions.
+    let mut edges = Vec::with_capacity(graph.edge_count());
+    for vertex in 0..vertices {
+        for (neighbor, &weight) in graph.neighbors(vertex) {
+            let mut weight = weight + potentials[vertex] - potentials[neighbor];
+
+            // Rounding of floating point weights may leave them slightly negative.
+            if weight < zero {
+                weight = zero;
+            }
+
+            edges.push((vertex, neighbor, weight));
+        }
+    }
+
+    let reweighted = CsrGraph::directed_from_edges(vertices, edges);
+
+    let all_paths = (0..vertices)
+        .map(|source| {
+            let mut paths = dijkstra_with_heap::<H, _>(&reweighted, source);
+
+            for (target, distance) in paths.distances.iter_mut().enumerate() {
+                *distance = distance.map(|d| d - potentials[source] + potentials[target]);
+            }
+
+            paths
+        })
+        .collect();
+
+    Ok(all_paths)
+}
+
+#[test]
+fn test_johnson() {
+    use graph::Graph;
+
+    let graph: Graph<(), _> = Graph::directed_from_edges(4,
+                                                         vec![(0, 1, -2),
+                                                              (1, 2, 3),
+                                                              (2, 0, 4),
+                                                              (0, 2, 2),
+                                                              (3, 2, -5)]);
+
+    let all_paths = johnson(&graph).unwrap();
+
+    assert_eq!(&[Some(0), Some(-2), Some(1), None], all_paths[0].distances());
+    assert_eq!(&[Some(-1), Some(-3), Some(-5), Some(0)], all_paths[3].distances());
+    assert_eq!(Some(vec![3, 2, 0, 1]), all_paths[3].path(1));
+}
+
+#[test]
+fn test_negative_cycle() {
+    use graph::Graph;
+
+    // The cycle isn't reachable from vertex 0, but every vertex is a source.
+    let graph: Graph<(), i32> = Graph::directed_from_edges(3, vec![(1, 2, 1), (2, 1, -2)]);
+
+    let mut cycle = johnson(&graph).unwrap_err();
+    cycle.sort();
+
+    assert_eq!(vec![1, 2], cycle);
+}
+
+#[test]
+fn test_random() {
+    use data_structures::heap::PairingHeap;
+    use graph::{Graph, random_edges};
+    use super::{bellman_ford, check_paths};
+
+    for seed in 1..21 {
+        let edges = random_edges(20, 40, -2, 20, seed);
+        let graph: Graph<(), i64> = Graph::directed_from_edges(20, edges);
+
+        let all_paths = match johnson_with_heap::<PairingHeap<_>, _>(&graph) {
+            Ok(all_paths) => all_paths,
+            Err(_) => continue,
+        };
+
+        for (source, paths) in all_paths.iter().enumerate() {
+            assert_eq!(source, paths.source());
+            assert_eq!(bellman_ford(&graph, source).unwrap().distances(),
+                       paths.distances());
+            check_paths(&graph, paths);
+        }
+    }
+}
+
+#[test]
+fn test_float() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::directed_from_edges(3, vec![(0, 1, 0.1f64), (1, 2, -0.3), (0, 2, 0.2)]);
+
+    let all_paths = johnson(&graph).unwrap();
+
+    assert!((all_paths[0].distance(2).unwrap() - -0.2).abs() < 1e-9);
+    assert_eq!(Some(1), all_paths[0].predecessor(2));
+}
@@ -0,0 +1,140 @@
+pub use self::dijkstra::{dijkstra, dijkstra_with_heap, dijkstra_with_lazy_heap};
+pub use self::dijkstra::{astar, astar_with_heap, astar_with_lazy_heap};
+pub use self::bellman_ford::{bellman_ford, spfa};
+pub use self::johnson::{johnson, johnson_with_heap};
+pub use self::floyd_warshall::{floyd_warshall, AllPairsShortestPaths};
+
+mod dijkstra;
+mod bellman_ford;
+mod johnson;
+mod floyd_warshall;
+
+/// Distances of the vertices of a graph from a source, with the tree of the shortest paths
+/// given by the predecessor of every vertex on its path.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<W> {
+    source: usize,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+    /// Create paths of a graph with `vertices` vertices where only `source` is reached.
+    fn new(source: usize, vertices: usize, zero: W) -> ShortestPaths<W> {
+        let mut distances = vec![None; vertices];
+        distances[source] = Some(zero);
+
+        ShortestPaths {
+            source: source,
+            distances: distances,
+            predecessors: vec![None; vertices],
+        }
+    }
+
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Return the distance from the source to `vertex`, or `None` if it's unreachable.
+    pub fn distance(&self, vertex: usize) -> Option<W> {
+        self.distances[vertex]
+    }
+
+    /// Return the vertex before `vertex` on the path from the source, or `None` for the source
+    /// and unreachable vertices.
+    pub fn predecessor(&self, vertex: usize) -> Option<usize> {
+        self.predecessors[vertex]
+    }
+
+    pub fn distances(&self) -> &[Option<W>] {
+        &self.distances
+    }
+
+    pub fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    /// Return the vertices of the path from the source to `target`, or `None` if it's
+    /// unreachable.
+    pub fn path(&self, target: usize) -> Option<Vec<usize>> {
+        if self.distances[target].is_none() {
+            return None;
+        }
+
+        let mut path = vec![target];
+        while let Some(predecessor) = self.predecessors[*path.last().unwrap()] {
+            path.push(predecessor);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Return the distances from `source` in a graph with `vertices` vertices and `edges`, by
+/// relaxing every edge `vertices` times.
+#[cfg(test)]
+fn reference_distances(vertices: usize,
+                       edges: &[(usize, usize, i64)],
+                       directed: bool,
+                       source: usize)
+                       -> Vec<Option<i64>> {
+    let mut distances = vec![None; vertices];
+    distances[source] = Some(0);
+
+    for _ in 0..vertices {
+        for &(s, t, w) in edges {
+            let mut relax = |from: usize, to: usize| {
+                if let Some(d) = distances[from] {
+                    if distances[to].map_or(true, |old| d + w < old) {
+                        distances[to] = Some(d + w);
+                    }
+                }
+            };
+
+            relax(s, t);
+            if !directed {
+                relax(t, s);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Check that the paths of `paths` lead along edges of `graph` and add up to their distances.
+#[cfg(test)]
+fn check_paths<G: ::graph::Adjacency<Weight = i64>>(graph: &G, paths: &ShortestPaths<i64>) {
+    for vertex in 0..graph.vertex_count() {
+        let path = match paths.path(vertex) {
+            None => continue,
+            Some(path) => path,
+        };
+
+        assert_eq!(paths.source(), path[0]);
+
+        let mut distance = 0;
+        for pair in path.windows(2) {
+            distance += graph.neighbors(pair[0])
+                .filter(|&(target, _)| target == pair[1])
+                .map(|(_, &weight)| weight)
+                .min()
+                .unwrap();
+        }
+
+        assert_eq!(paths.distance(vertex), Some(distance));
+    }
+}
+
+#[test]
+fn test_path() {
+    let paths = ShortestPaths {
+        source: 2,
+        distances: vec![Some(3), None, Some(0), Some(1)],
+        predecessors: vec![Some(3), None, None, Some(2)],
+    };
+
+    assert_eq!(Some(vec![2, 3, 0]), paths.path(0));
+    assert_eq!(Some(vec![2]), paths.path(2));
+    assert_eq!(None, paths.path(1));
+}
@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use graph::Adjacency;
+
+/// Iterator over the vertices reachable from a source in breadth-first order, which visits
+/// vertices in the order of their numbers of edges from the source.
+pub struct Bfs<'a, G: Adjacency + 'a> {
+    graph: &'a G,
+    queue: VecDeque<usize>,
+    is_discovered: Vec<bool>,
+}
+
+impl<'a, G: Adjacency> Bfs<'a, G> {
+    /// Create an iterator over `graph` from `source`.
+    ///
+    /// ##Panics
+    /// If `source` isn't a vertex of `graph`.
+    pub fn new(graph: &'a G, source: usize) -> Bfs<'a, G> {
+        assert!(source < graph.vertex_count(),
+                "Called new with source = {} >= vertices = {}",
+                source,
+                graph.vertex_count());
+
+        let mut is_discovered = vec![false; graph.vertex_count()];
+        is_discovered[source] = true;
+
+        Bfs {
+            graph: graph,
+            queue: vec![source].into_iter().collect(),
+            is_discovered: is_discovered,
+        }
+    }
+
+    /// Return `true` if `vertex` was returned or will be returned by the iterator.
+    pub fn is_discovered(&self, vertex: usize) -> bool {
+        self.is_discovered[vertex]
+    }
+}
+
+impl<'a, G: Adjacency> Iterator for Bfs<'a, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let vertex = match self.queue.pop_front() {
+            None => return None,
+            Some(vertex) => vertex,
+        };
+
+        for (neighbor, _) in self.graph.neighbors(vertex) {
+            if !self.is_discovered[neighbor] {
+                self.is_discovered[neighbor] = true;
+                self.queue.push_back(neighbor);
+            }
+        }
+
+        Some(vertex)
+    }
+}
+
+#[test]
+fn test_bfs() {
+    use graph::Graph;
+
+    let graph: Graph<(), ()> = Graph::directed_from_edges(7,
+                                                          vec![(0, 1, ()),
+                                                               (0, 2, ()),
+                                                               (1, 3, ()),
+                                                               (2, 3, ()),
+                                                               (3, 0, ()),
+                                                               (2, 4, ()),
+                                                               (4, 5, ()),
+                                                               (6, 0, ())]);
+
+    assert_eq!(vec![0, 1, 2, 3, 4, 5], Bfs::new(&graph, 0).collect::<Vec<_>>());
+    assert_eq!(vec![4, 5], Bfs::new(&graph, 4).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_lazy() {
+    use graph::CsrGraph;
+
+    // Path of many vertices, of which only the first ones are visited.
+    let graph = CsrGraph::undirected_from_edges(100000, (1..100000).map(|v| (v - 1, v, ())));
+
+    let mut bfs = Bfs::new(&graph, 50000);
+
+    assert_eq!(vec![50000, 49999, 50001], bfs.by_ref().take(3).collect::<Vec<_>>());
+    assert!(bfs.is_discovered(50002));
+    assert!(!bfs.is_discovered(50003));
+}
@@ -0,0 +1,189 @@
+// Depth-first search with a stack of neighbour iterators instead of recursion, so deep graphs
+// don't overflow the call stack. Every vertex on the stack resumes its neighbours where it left
+// them, which visits vertices in the same order as the recursive search.
+
+use graph::{Adjacency, Neighbors};
+
+/// Step of a depth-first search.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DfsEvent {
+    /// The vertex is visited for the first time, before its descendants. These come in
+    /// pre-order.
+    Discover(usize),
+
+    /// All descendants of the vertex were visited. These come in post-order.
+    Finish(usize),
+}
+
+/// Iterator over the events of a depth-first search from a source.
+pub struct DfsEvents<'a, G: Adjacency + 'a> {
+    graph: &'a G,
+    stack: Vec<(usize, Neighbors<'a, G::Weight>)>,
+    is_discovered: Vec<bool>,
+    source: Option<usize>,
+}
+
+/// Iterator over the vertices reachable from a source in depth-first pre-order.
+pub struct Dfs<'a, G: Adjacency + 'a> {
+    events: DfsEvents<'a, G>,
+}
+
+impl<'a, G: Adjacency> DfsEvents<'a, G> {
+    /// Create an iterator over `graph` from `source`.
+    ///
+    /// ##Panics
+    /// If `source` isn't a vertex of `graph`.
+    pub fn new(graph: &'a G, source: usize) -> DfsEvents<'a, G> {
+        assert!(source < graph.vertex_count(),
+                "Called new with source = {} >= vertices = {}",
+                source,
+                graph.vertex_count());
+
+        DfsEvents {
+            graph: graph,
+            stack: Vec::new(),
+            is_discovered: vec![false; graph.vertex_count()],
+            source: Some(source),
+        }
+    }
+
+    /// Return `true` if `vertex` was discovered.
+    pub fn is_discovered(&self, vertex: usize) -> bool {
+        self.is_discovered[vertex]
+    }
+
+    /// Return the vertices discovered but not finished, from the source to the last discovered
+    /// one. These are the path to the last discovered vertex.
+    pub fn path(&self) -> Vec<usize> {
+        self.stack.iter().map(|&(vertex, _)| vertex).collect()
+    }
+
+    fn discover(&mut self, vertex: usize) -> DfsEvent {
+        self.is_discovered[vertex] = true;
+        self.stack.push((vertex, self.graph.neighbors(vertex)));
+
+        DfsEvent::Discover(vertex)
+    }
+}
+
+impl<'a, G: Adjacency> Iterator for DfsEvents<'a, G> {
+    type Item = DfsEvent;
+
+    fn next(&mut self) -> Option<DfsEvent> {
+        if let Some(source) = self.source.take() {
+            return Some(self.discover(source));
+        }
+
+        let next = {
+            let is_discovered = &self.is_discovered;
+
+            match self.stack.last_mut() {
+                None => return None,
+                Some(&mut (vertex, ref mut neighbors)) => {
+                    neighbors.find(|&(neighbor, _)| !is_discovered[neighbor])
+                        .map(|(neighbor, _)| neighbor)
+                        .ok_or(vertex)
+                }
+            }
+        };
+
+        match next {
+            Ok(neighbor) => Some(self.discover(neighbor)),
+            Err(vertex) => {
+                self.stack.pop();
+                Some(DfsEvent::Finish(vertex))
+            }
+        }
+    }
+}
+
+impl<'a, G: Adjacency> Dfs<'a, G> {
+    /// Create an iterator over `graph` from `source`.
+    ///
+    /// ##Panics
+    /// If `source` isn't a vertex of `graph`.
+    pub fn new(graph: &'a G, source: usize) -> Dfs<'a, G> {
+        Dfs { events: DfsEvents::new(graph, source) }
+    }
+
+    /// Return `true` if `vertex` was returned by the iterator.
+    pub fn is_discovered(&self, vertex: usize) -> bool {
+        self.events.is_discovered(vertex)
+    }
+}
+
+impl<'a, G: Adjacency> Iterator for Dfs<'a, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for event in &mut self.events {
+            if let DfsEvent::Discover(vertex) = event {
+                return Some(vertex);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+fn test_graph() -> ::graph::Graph<(), ()> {
+    ::graph::Graph::directed_from_edges(7,
+                                        vec![(0, 1, ()),
+                                             (0, 2, ()),
+                                             (1, 3, ()),
+                                             (2, 3, ()),
+                                             (3, 0, ()),
+                                             (2, 4, ()),
+                                             (4, 5, ()),
+                                             (6, 0, ())])
+}
+
+#[test]
+fn test_dfs() {
+    let graph = test_graph();
+
+    assert_eq!(vec![0, 1, 3, 2, 4, 5], Dfs::new(&graph, 0).collect::<Vec<_>>());
+    assert_eq!(vec![6, 0, 1, 3, 2, 4, 5], Dfs::new(&graph, 6).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_events() {
+    use self::DfsEvent::*;
+
+    let graph = test_graph();
+
+    assert_eq!(vec![Discover(0),
+                    Discover(1),
+                    Discover(3),
+                    Finish(3),
+                    Finish(1),
+                    Discover(2),
+                    Discover(4),
+                    Discover(5),
+                    Finish(5),
+                    Finish(4),
+                    Finish(2),
+                    Finish(0)],
+               DfsEvents::new(&graph, 0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_path() {
+    let graph = test_graph();
+    let mut events = DfsEvents::new(&graph, 0);
+
+    assert_eq!(Some(DfsEvent::Discover(5)),
+               events.find(|&event| event == DfsEvent::Discover(5)));
+    assert_eq!(vec![0, 2, 4, 5], events.path());
+}
+
+#[test]
+fn test_deep() {
+    use graph::CsrGraph;
+
+    let graph = CsrGraph::directed_from_edges(1000000, (1..1000000).map(|v| (v - 1, v, ())));
+
+    assert_eq!(1000000, Dfs::new(&graph, 0).count());
+    assert_eq!(2000000, DfsEvents::new(&graph, 0).count());
+}
@@ -0,0 +1,7 @@
+pub use self::bfs::Bfs;
+pub use self::dfs::{Dfs, DfsEvents, DfsEvent};
+pub use self::topological_sort::{topological_sort_kahn, topological_sort_dfs};
+
+mod bfs;
+mod dfs;
+mod topological_sort;
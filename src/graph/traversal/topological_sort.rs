@@ -0,0 +1,225 @@
+// Topological sorts of directed graphs, which order vertices so that every edge goes forward.
+//
+// Kahn's algorithm repeatedly removes vertices without incoming edges. If vertices are left,
+// each of them has an incoming edge from another one left, so walking these edges backwards
+// repeats a vertex on a cycle. The depth-first version orders vertices by decreasing finishing
+// times, and an edge to a vertex which is discovered but not finished closes a cycle.
+
+use graph::{Adjacency, Neighbors, check_directed};
+
+/// Return the vertices of `graph` in a topological order using Kahn's algorithm, or the
+/// vertices of a cycle in the order of its edges.
+///
+/// ##Panics
+/// If `graph` is undirected.
+pub fn topological_sort_kahn<G: Adjacency>(graph: &G) -> Result<Vec<usize>, Vec<usize>> {
+    check_directed(graph, "topological_sort_kahn");
+
+    let vertices = graph.vertex_count();
+
+    let mut in_degrees = vec![0; vertices];
+    for (_, target, _) in graph.edges() {
+        in_degrees[target] += 1;
+    }
+
+    let mut order: Vec<_> = (0..vertices).filter(|&vertex| in_degrees[vertex] == 0).collect();
+    let mut next = 0;
+
+    while next < order.len() {
+        let vertex = order[next];
+        next += 1;
+
+        for (neighbor, _) in graph.neighbors(vertex) {
+            in_degrees[neighbor] -= 1;
+
+            if in_degrees[neighbor] == 0 {
+                order.push(neighbor);
+            }
+        }
+    }
+
+    if order.len() == vertices {
+        return Ok(order);
+    }
+
+    // One incoming edge of every vertex left, from another vertex left.
+    let mut predecessors = vec![None; vertices];
+    for vertex in 0..vertices {
+        if in_degrees[vertex] > 0 {
+            for (neighbor, _) in graph.neighbors(vertex) {
+                if in_degrees[neighbor] > 0 {
+                    predecessors[neighbor] = Some(vertex);
+                }
+            }
+        }
+    }
+
+    let mut is_visited = vec![false; vertices];
+    let mut vertex = (0..vertices).find(|&vertex| in_degrees[vertex] > 0).unwrap();
+
+    while !is_visited[vertex] {
+        is_visited[vertex] = true;
+        vertex = predecessors[vertex].unwrap();
+    }
+
+    let mut cycle = vec![vertex];
+    let mut current = predecessors[vertex].unwrap();
+
+    while current != vertex {
+        cycle.push(current);
+        current = predecessors[current].unwrap();
+    }
+
+    cycle.reverse();
+    Err(cycle)
+}
+
+/// Return the vertices of `graph` in a topological order using depth-first search, or the
+/// vertices of a cycle in the order of its edges.
+///
+/// ##Panics
+/// If `graph` is undirected.
+pub fn topological_sort_dfs<G: Adjacency>(graph: &G) -> Result<Vec<usize>, Vec<usize>> {
+    check_directed(graph, "topological_sort_dfs");
+
+    let vertices = graph.vertex_count();
+
+    let mut is_discovered = vec![false; vertices];
+    let mut is_finished = vec![false; vertices];
+    let mut order = Vec::with_capacity(vertices);
+    let mut stack: Vec<(usize, Neighbors<G::Weight>)> = Vec::new();
+
+    for root in 0..vertices {
+        if is_discovered[root] {
+            continue;
+        }
+
+        is_discovered[root] = true;
+        stack.push((root, graph.neighbors(root)));
+
+        while let Some(&mut (vertex, ref mut neighbors)) = stack.last_mut() {
+            let neighbor = match neighbors.next() {
+                None => {
+                    is_finished[vertex] = true;
+                    order.push(vertex);
+                    stack.pop();
+                    continue;
+                }
+                Some((neighbor, _)) => neighbor,
+            };
+
+            if !is_discovered[neighbor] {
+                is_discovered[neighbor] = true;
+                stack.push((neighbor, graph.neighbors(neighbor)));
+            } else if !is_finished[neighbor] {
+                // The neighbor is on the stack, and the stack above it is a path back to it.
+                let start = stack.iter().position(|&(v, _)| v == neighbor).unwrap();
+
+                return Err(stack[start..].iter().map(|&(v, _)| v).collect());
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// Check that `order` is a topological order of `graph`.
+#[cfg(test)]
+fn check_order<G: Adjacency>(graph: &G, order: &[usize]) {
+    let mut positions = vec![None; graph.vertex_count()];
+    for (position, &vertex) in order.iter().enumerate() {
+        assert!(positions[vertex].is_none());
+        positions[vertex] = Some(position);
+    }
+
+    assert_eq!(graph.vertex_count(), order.len());
+    for (source, target, _) in graph.edges() {
+        assert!(positions[source] < positions[target]);
+    }
+}
+
+/// Check that `cycle` is a cycle of edges of `graph`.
+#[cfg(test)]
+fn check_cycle<G: Adjacency>(graph: &G, cycle: &[usize]) {
+    assert!(!cycle.is_empty());
+
+    for (i, &vertex) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+
+        assert!(graph.neighbors(vertex).any(|(neighbor, _)| neighbor == next));
+    }
+}
+
+#[test]
+fn test_topological_sort() {
+    use graph::Graph;
+
+    let graph: Graph<(), ()> = Graph::directed_from_edges(6,
+                                                          vec![(5, 2, ()),
+                                                               (5, 0, ()),
+                                                               (4, 0, ()),
+                                                               (4, 1, ()),
+                                                               (2, 3, ()),
+                                                               (3, 1, ())]);
+
+    assert_eq!(Ok(vec![4, 5, 2, 0, 3, 1]), topological_sort_kahn(&graph));
+    assert_eq!(Ok(vec![5, 4, 2, 3, 1, 0]), topological_sort_dfs(&graph));
+}
+
+#[test]
+fn test_cycle() {
+    use graph::Graph;
+
+    let graph: Graph<(), ()> = Graph::directed_from_edges(6,
+                                                          vec![(0, 1, ()),
+                                                               (1, 2, ()),
+                                                               (2, 3, ()),
+                                                               (3, 1, ()),
+                                                               (3, 4, ()),
+                                                               (5, 5, ())]);
+
+    let mut cycle = topological_sort_kahn(&graph).unwrap_err();
+    check_cycle(&graph, &cycle);
+    cycle.sort();
+    assert!(cycle == vec![1, 2, 3] || cycle == vec![5]);
+
+    assert_eq!(Err(vec![1, 2, 3]), topological_sort_dfs(&graph));
+}
+
+#[test]
+fn test_random() {
+    use graph::{CsrGraph, random_edges};
+
+    let mut cycles = 0;
+
+    for seed in 1..101 {
+        let edges = random_edges(30, 25 + seed as usize / 4, 0, 1, seed);
+        let graph = CsrGraph::directed_from_edges(30, edges);
+
+        match (topological_sort_kahn(&graph), topological_sort_dfs(&graph)) {
+            (Ok(x), Ok(y)) => {
+                check_order(&graph, &x);
+                check_order(&graph, &y);
+            }
+            (Err(x), Err(y)) => {
+                check_cycle(&graph, &x);
+                check_cycle(&graph, &y);
+                cycles += 1;
+            }
+            _ => panic!("Only one of the sorts found a cycle"),
+        }
+    }
+
+    assert!(cycles > 0 && cycles < 100);
+}
+
+#[test]
+#[should_panic]
+fn test_undirected() {
+    use graph::Graph;
+
+    let graph: Graph<(), ()> = Graph::undirected_from_edges(2, vec![(0, 1, ())]);
+
+    topological_sort_kahn(&graph).ok();
+}
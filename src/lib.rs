@@ -10,3 +10,4 @@ pub mod select;
 pub mod search;
 pub mod sort;
 pub mod data_structures;
+pub mod graph;